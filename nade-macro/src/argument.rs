@@ -1,8 +1,11 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
+    braced,
     parse::{Parse, ParseStream},
-    Expr, Pat, Token,
+    parse_quote,
+    punctuated::Punctuated,
+    token, Expr, Ident, Pat, PatIdent, Token,
 };
 
 pub(crate) enum Argument {
@@ -14,6 +17,17 @@ pub(crate) enum Argument {
         eq_token: Token![=],
         value: Expr,
     },
+    // `_`, uses the default argument of the parameter at its position
+    Placeholder {
+        underscore_token: Token![_],
+    },
+    // `..expr` or `..expr { a, b }`, passes the fields of `expr` to the parameters that are not
+    // specified, all of them or only the listed ones
+    Spread {
+        dot2_token: Token![..],
+        expr: Expr,
+        fields: Option<(token::Brace, Punctuated<Ident, Token![,]>)>,
+    },
 }
 
 impl Parse for Argument {
@@ -23,11 +37,67 @@ impl Parse for Argument {
             test.call(Pat::parse_single).is_ok() && test.peek(Token![=])
         };
 
-        let argument = if is_named {
+        let is_placeholder = {
+            let test = input.fork();
+            test.parse::<Token![_]>().is_ok() && (test.is_empty() || test.peek(Token![,]))
+        };
+
+        // `..=` is always a range
+        let is_spread = input.peek(Token![..]) && !input.peek(Token![..=]);
+
+        let argument = if is_spread {
+            let dot2_token = input.parse::<Token![..]>()?;
+            let expr = input.call(Expr::parse_without_eager_brace)?;
+
+            let fields = if input.peek(token::Brace) {
+                let content;
+                let brace_token = braced!(content in input);
+                Some((
+                    brace_token,
+                    content.parse_terminated(Ident::parse, Token![,])?,
+                ))
+            } else {
+                None
+            };
+
+            Argument::Spread {
+                dot2_token,
+                expr,
+                fields,
+            }
+        } else if is_placeholder {
+            Argument::Placeholder {
+                underscore_token: input.parse::<Token![_]>()?,
+            }
+        } else if is_named {
+            let pattern = input.call(Pat::parse_single)?;
+            let eq_token = input.parse::<Token![=]>()?;
+
+            // `name=` is the shorthand of `name = name`, like the field init shorthand
+            let value = if input.is_empty() || input.peek(Token![,]) {
+                match &pattern {
+                    Pat::Ident(PatIdent {
+                        by_ref: None,
+                        mutability: None,
+                        ident,
+                        subpat: None,
+                        ..
+                    }) => parse_quote!(#ident),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            pattern,
+                            "the shorthand `name=` can only be used with an identifier",
+                        ))
+                    }
+                }
+            } else {
+                input.parse::<Expr>()?
+            };
+
             Argument::Named {
-                pattern: input.call(Pat::parse_single)?,
-                eq_token: input.parse::<Token![=]>()?,
-                value: input.parse::<Expr>()?,
+                pattern,
+                eq_token,
+                value,
             }
         } else {
             Argument::Positioned {
@@ -39,6 +109,27 @@ impl Parse for Argument {
     }
 }
 
+impl Argument {
+    pub(crate) fn value(&self) -> Option<&Expr> {
+        match self {
+            Argument::Positioned { value } | Argument::Named { value, .. } => Some(value),
+            Argument::Placeholder { .. } | Argument::Spread { .. } => None,
+        }
+    }
+
+    /// Whether a spread argument passes its field to the parameter with the binding identifier.
+    pub(crate) fn spreads(&self, ident: &Ident) -> bool {
+        match self {
+            Argument::Spread { fields: None, .. } => true,
+            Argument::Spread {
+                fields: Some((_, fields)),
+                ..
+            } => fields.iter().any(|field| field == ident),
+            _ => false,
+        }
+    }
+}
+
 impl ToTokens for Argument {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
@@ -52,6 +143,18 @@ impl ToTokens for Argument {
                 eq_token.to_tokens(tokens);
                 value.to_tokens(tokens);
             }
+            Argument::Placeholder { underscore_token } => underscore_token.to_tokens(tokens),
+            Argument::Spread {
+                dot2_token,
+                expr,
+                fields,
+            } => {
+                dot2_token.to_tokens(tokens);
+                expr.to_tokens(tokens);
+                if let Some((brace_token, fields)) = fields {
+                    brace_token.surround(tokens, |tokens| fields.to_tokens(tokens));
+                }
+            }
         }
     }
 }
@@ -0,0 +1,355 @@
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    spanned::Spanned, GenericParam, Ident, ItemFn, Lifetime, LifetimeParam, Pat, PatIdent,
+    ReturnType, Type,
+};
+
+use crate::{parameter::Parameter, parameter_doc::ParameterDoc};
+
+struct BuilderField<'a> {
+    ident: &'a Ident,
+    // elided lifetimes are replaced with lifetimes of the builder
+    ty: Type,
+    parameter: &'a Parameter,
+    parameter_doc: &'a ParameterDoc,
+    // type parameter of the builder that records whether a required parameter is set
+    state: Option<Ident>,
+}
+
+pub(crate) fn generate(
+    fun: &ItemFn,
+    parameters: &[Parameter],
+    parameter_docs: &[ParameterDoc],
+) -> syn::Result<TokenStream> {
+    let ItemFn { vis, sig, .. } = fun;
+
+    let name = &sig.ident;
+    let builder_name = format_ident!("{}Builder", to_camel_case(&name.to_string()));
+
+    let mut fields = Vec::with_capacity(parameters.len());
+    let mut fresh_lifetimes: Vec<Lifetime> = Vec::new();
+
+    for (idx, (parameter, parameter_doc)) in parameters.iter().zip(parameter_docs).enumerate() {
+        let ident = match (&parameter.name, &parameter.pat) {
+            (Some(name), _) => name,
+            (
+                None,
+                Pat::Ident(PatIdent {
+                    ident,
+                    by_ref: None,
+                    subpat: None,
+                    ..
+                }),
+            ) => ident,
+            (None, pat) => {
+                return Err(syn::Error::new(
+                    pat.span(),
+                    "the builder only supports parameters whose pattern is an identifier \
+                        or that have a `#[nade(name = ..)]`",
+                ));
+            }
+        };
+
+        if contains_impl_trait(parameter.ty.to_token_stream()) {
+            return Err(syn::Error::new(
+                parameter.ty.span(),
+                "the builder does not support `impl Trait` parameters",
+            ));
+        }
+
+        let state = match parameter.default {
+            Some(_) => None,
+            None => Some(format_ident!("__S{}", idx)),
+        };
+
+        let ty = replace_elided_lifetimes(parameter.ty.to_token_stream(), &mut || {
+            let lifetime = Lifetime::new(
+                &format!("'__nade{}", fresh_lifetimes.len()),
+                Span::call_site(),
+            );
+            fresh_lifetimes.push(lifetime.clone());
+            lifetime
+        });
+
+        fields.push(BuilderField {
+            ident,
+            ty: syn::parse2(ty)?,
+            parameter,
+            parameter_doc,
+            state,
+        });
+    }
+
+    let mut generics = sig.generics.clone();
+
+    for lifetime in fresh_lifetimes.iter().rev() {
+        generics.params.insert(
+            0,
+            GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+        );
+    }
+
+    let output = match (&sig.output, fresh_lifetimes.as_slice()) {
+        // same as the lifetime elision rules, if there is exactly one input lifetime,
+        // it is assigned to all elided output lifetimes.
+        (ReturnType::Type(arrow, ty), [lifetime]) if sig.generics.lifetimes().next().is_none() => {
+            let ty = replace_elided_lifetimes(ty.to_token_stream(), &mut || lifetime.clone());
+            quote!(#arrow #ty)
+        }
+        (output, _) => output.to_token_stream(),
+    };
+
+    let bare_params = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(l) => l.lifetime.to_token_stream(),
+            GenericParam::Type(t) => t.ident.to_token_stream(),
+            GenericParam::Const(c) => {
+                let ident = &c.ident;
+                let ty = &c.ty;
+                quote!(const #ident: #ty)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let generic_args = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(l) => l.lifetime.to_token_stream(),
+            GenericParam::Type(t) => t.ident.to_token_stream(),
+            GenericParam::Const(c) => c.ident.to_token_stream(),
+        })
+        .collect::<Vec<_>>();
+
+    let phantoms = generics.params.iter().filter_map(|param| match param {
+        GenericParam::Lifetime(l) => {
+            let lifetime = &l.lifetime;
+            Some(quote!(&#lifetime ()))
+        }
+        GenericParam::Type(t) => Some(t.ident.to_token_stream()),
+        GenericParam::Const(_) => None,
+    });
+
+    let states = fields
+        .iter()
+        .filter_map(|field| field.state.as_ref())
+        .collect::<Vec<_>>();
+
+    let field_idents = fields.iter().map(|field| field.ident).collect::<Vec<_>>();
+
+    let field_tys = fields.iter().map(|field| {
+        let ty = &field.ty;
+        match &field.state {
+            Some(state) => state.to_token_stream(),
+            None => quote!(::core::option::Option<#ty>),
+        }
+    });
+
+    let field_inits = fields.iter().map(|field| match field.state {
+        Some(_) => quote!(()),
+        None => quote!(::core::option::Option::None),
+    });
+
+    let unset_states = states.iter().map(|_| quote!(())).collect::<Vec<_>>();
+
+    let builder_doc = format!("Builder for function [`{}`]({}()).", name, name);
+
+    let optional_setters = fields
+        .iter()
+        .filter(|field| field.state.is_none())
+        .map(|field| {
+            let ident = field.ident;
+            let ty = &field.ty;
+            let docs = &field.parameter_doc.docs;
+
+            quote! {
+                #(#[doc = #docs])*
+                pub fn #ident(mut self, #ident: #ty) -> Self {
+                    self.#ident = ::core::option::Option::Some(#ident);
+                    self
+                }
+            }
+        });
+
+    let required_setters = fields.iter().filter(|field| field.state.is_some()).map(|field| {
+        let ident = field.ident;
+        let ty = &field.ty;
+        let docs = &field.parameter_doc.docs;
+
+        let other_states = states.iter().filter(|state| Some(**state) != field.state.as_ref());
+
+        let before_states = states.iter().map(|state| {
+            if Some(*state) == field.state.as_ref() {
+                quote!(())
+            } else {
+                state.to_token_stream()
+            }
+        });
+
+        let after_states = states.iter().map(|state| {
+            if Some(*state) == field.state.as_ref() {
+                quote!((#ty,))
+            } else {
+                state.to_token_stream()
+            }
+        });
+
+        let moves = field_idents.iter().map(|field_ident| {
+            if *field_ident == ident {
+                quote!(#ident: (#ident,))
+            } else {
+                quote!(#field_ident: self.#field_ident)
+            }
+        });
+
+        quote! {
+            impl<#(#bare_params,)* #(#other_states,)*> #builder_name<#(#generic_args,)* #(#before_states,)*> {
+                #(#[doc = #docs])*
+                pub fn #ident(self, #ident: #ty) -> #builder_name<#(#generic_args,)* #(#after_states,)*> {
+                    #builder_name {
+                        #(#moves,)*
+                        __marker: ::core::marker::PhantomData,
+                    }
+                }
+            }
+        }
+    });
+
+    let set_states = fields
+        .iter()
+        .filter(|field| field.state.is_some())
+        .map(|field| {
+            let ty = &field.ty;
+            quote!((#ty,))
+        });
+
+    // bound in parameter order, so that default arguments can refer to earlier parameters
+    let bindings = fields.iter().map(|field| {
+        let ident = field.ident;
+        match &field.parameter.default {
+            Some((_, default)) => {
+                let default = default.inner();
+                quote!(let #ident = self.#ident.unwrap_or_else(|| #default);)
+            }
+            None => quote!(let #ident = self.#ident.0;),
+        }
+    });
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let asyncness = &sig.asyncness;
+    let unsafety = &sig.unsafety;
+    let await_token = asyncness.map(|_| quote!(.await));
+
+    let expand = quote! {
+        #[doc = #builder_doc]
+        #[must_use]
+        #vis struct #builder_name<#(#bare_params,)* #(#states,)*> {
+            #(#field_idents: #field_tys,)*
+            __marker: ::core::marker::PhantomData<fn() -> (#(#phantoms,)*)>,
+        }
+
+        impl<#(#bare_params,)*> #builder_name<#(#generic_args,)* #(#unset_states,)*> {
+            pub fn new() -> Self {
+                #builder_name {
+                    #(#field_idents: #field_inits,)*
+                    __marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<#(#bare_params,)*> ::core::default::Default for #builder_name<#(#generic_args,)* #(#unset_states,)*> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<#(#bare_params,)* #(#states,)*> #builder_name<#(#generic_args,)* #(#states,)*> {
+            #(#optional_setters)*
+        }
+
+        #(#required_setters)*
+
+        impl #impl_generics #builder_name<#(#generic_args,)* #(#set_states,)*> #where_clause {
+            pub #asyncness #unsafety fn call(self) #output {
+                #(#bindings)*
+                #name(#(#field_idents,)*) #await_token
+            }
+        }
+    };
+
+    Ok(expand)
+}
+
+pub(crate) fn contains_impl_trait(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(ident) => ident == "impl",
+        TokenTree::Group(group) => contains_impl_trait(group.stream()),
+        _ => false,
+    })
+}
+
+pub(crate) fn replace_elided_lifetimes(
+    tokens: TokenStream,
+    new_lifetime: &mut dyn FnMut() -> Lifetime,
+) -> TokenStream {
+    let trees = tokens.into_iter().collect::<Vec<_>>();
+    let mut replaced = TokenStream::new();
+    let mut idx = 0;
+
+    while idx < trees.len() {
+        match &trees[idx] {
+            TokenTree::Punct(p) if p.as_char() == '&' => {
+                p.to_tokens(&mut replaced);
+
+                if !matches!(trees.get(idx + 1), Some(TokenTree::Punct(p)) if p.as_char() == '\'') {
+                    new_lifetime().to_tokens(&mut replaced);
+                }
+            }
+            TokenTree::Punct(p)
+                if p.as_char() == '\''
+                    && matches!(trees.get(idx + 1), Some(TokenTree::Ident(i)) if i == "_") =>
+            {
+                new_lifetime().to_tokens(&mut replaced);
+                idx += 1;
+            }
+            // elided lifetimes in `Fn(&str)` and `fn(&str)` are higher-ranked
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Parenthesis
+                    && idx > 0
+                    && matches!(trees[idx - 1], TokenTree::Ident(_)) =>
+            {
+                group.to_tokens(&mut replaced);
+            }
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    replace_elided_lifetimes(group.stream(), new_lifetime),
+                );
+                new_group.set_span(group.span());
+                new_group.to_tokens(&mut replaced);
+            }
+            tree => tree.to_tokens(&mut replaced),
+        }
+
+        idx += 1;
+    }
+
+    replaced
+}
+
+// `foo_bar` => `FooBar`
+fn to_camel_case(s: &str) -> String {
+    s.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
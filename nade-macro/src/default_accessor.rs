@@ -0,0 +1,397 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, GenericParam, Generics, Ident, Lifetime, Path,
+    Visibility, WhereClause,
+};
+
+use crate::{
+    builder::{contains_impl_trait, replace_elided_lifetimes},
+    maybe_start_with_dollar::MaybeStartWithDollar,
+    nade_impl::replace_self,
+    parameter::Parameter,
+};
+
+/// Where the hidden accessor functions of a function are, and how the macro calls them.
+pub(crate) enum Holder<'a> {
+    /// A struct with the name of the function, `foo::a()`, `module_path` is the prefix that reaches
+    /// it from the call site, e.g. `$crate::module::`. It is in the type namespace while the
+    /// function is in the value namespace, so importing the function imports it as well.
+    Fn {
+        name: Ident,
+        module_path: Option<&'a TokenStream>,
+    },
+    /// A value returned by a hidden associated function named after the method,
+    /// `Type::__nade_defaults_foo().a()`, reachable through the same path as the method.
+    Method {
+        module_path: Option<&'a TokenStream>,
+        self_path: &'a Path,
+        name: Ident,
+    },
+}
+
+/// Generates a hidden accessor function for each default argument that can be evaluated where
+/// it is written, and replaces the default argument with a call to the accessor. The accessors
+/// are reached through the same path as the function, so they resolve wherever the macro can
+/// call the function.
+///
+/// The other default arguments, e.g. the ones that refer to earlier parameters, are evaluated at
+/// the call site. Those that don't depend on generic parameters get a hidden check function, so
+/// that a default argument of the wrong type is reported when the defining crate compiles, not at
+/// the call site. The items are placed next to the function, the check functions of a method in
+/// its `impl` block.
+pub(crate) fn generate(
+    fn_name: &Ident,
+    vis: &Visibility,
+    generics: &Generics,
+    holder: Holder,
+    parameters: &mut [Parameter],
+) -> syn::Result<(Vec<TokenStream>, Vec<TokenStream>)> {
+    let binding_idents = parameters
+        .iter()
+        .filter_map(|param| param.binding_ident().cloned())
+        .collect::<Vec<_>>();
+
+    let mut accessors = Vec::new();
+    let mut checks = Vec::new();
+
+    for idx in 0..parameters.len() {
+        let (earlier, rest) = parameters.split_at_mut(idx);
+        let parameter = &mut rest[0];
+
+        let Some((_, default)) = &parameter.default else {
+            continue;
+        };
+
+        let span = default.span();
+
+        let Some((ident, accessor_generics)) = accessor_of(generics, &binding_idents, parameter)
+        else {
+            checks.extend(generate_check(fn_name, idx, generics, earlier, parameter));
+            continue;
+        };
+
+        let call = match &holder {
+            Holder::Fn { name, module_path } => quote_spanned!(span=> #module_path #name::#ident()),
+            Holder::Method {
+                module_path,
+                self_path,
+                name,
+            } => quote_spanned!(span=> #module_path #self_path::#name().#ident()),
+        };
+
+        let receiver = matches!(holder, Holder::Method { .. }).then(|| quote!(self));
+        // `Self` in the accessor is the hidden struct, not the type of the method
+        let ty = match &holder {
+            Holder::Method { self_path, .. } => {
+                replace_self(parameter.ty.to_token_stream(), self_path)
+            }
+            Holder::Fn { .. } => parameter.ty.to_token_stream(),
+        };
+        let default = default.inner();
+        let where_clause = &accessor_generics.where_clause;
+
+        accessors.push(quote_spanned! {span=>
+            #[allow(dead_code)]
+            #vis fn #ident #accessor_generics(#receiver) -> #ty #where_clause {
+                #default
+            }
+        });
+
+        parameter.default.as_mut().unwrap().1 = syn::parse2(call)?;
+    }
+
+    let items = match holder {
+        _ if accessors.is_empty() => Vec::new(),
+        Holder::Fn { name, .. } => vec![quote! {
+            #[doc(hidden)]
+            #[allow(dead_code, non_camel_case_types)]
+            #vis struct #name {}
+
+            impl #name {
+                #(#accessors)*
+            }
+        }],
+        // the type of the value can't be named, so the accessors are keyed on the method name only
+        Holder::Method {
+            self_path, name, ..
+        } => vec![quote! {
+            const _: () = {
+                #[doc(hidden)]
+                pub struct __nade_defaults;
+
+                impl __nade_defaults {
+                    #(#accessors)*
+                }
+
+                impl #self_path {
+                    #[doc(hidden)]
+                    #[allow(dead_code)]
+                    #vis fn #name() -> __nade_defaults {
+                        __nade_defaults
+                    }
+                }
+            };
+        }],
+    };
+
+    Ok((items, checks))
+}
+
+// the accessor has no parameters to refer to
+fn accessor_of(
+    generics: &Generics,
+    binding_idents: &[Ident],
+    parameter: &Parameter,
+) -> Option<(Ident, Generics)> {
+    let Some((_, MaybeStartWithDollar::Normal(default))) = &parameter.default else {
+        return None;
+    };
+
+    let ident = parameter.binding_ident()?;
+
+    if contains_impl_trait(parameter.ty.to_token_stream())
+        || contains_elided_lifetime(parameter.ty.to_token_stream())
+        || binding_idents
+            .iter()
+            .any(|binding_ident| parameter.default_refers_to(binding_ident))
+    {
+        return None;
+    }
+
+    let accessor_generics = accessor_generics(
+        generics,
+        parameter.ty.to_token_stream(),
+        default.to_token_stream(),
+    )?;
+
+    Some((ident.clone(), accessor_generics))
+}
+
+/// Generates the hidden check functions only, for the default arguments that don't mention
+/// `generics`.
+pub(crate) fn generate_checks(
+    fn_name: &Ident,
+    generics: &Generics,
+    parameters: &[Parameter],
+) -> Vec<TokenStream> {
+    (0..parameters.len())
+        .filter_map(|idx| {
+            generate_check(fn_name, idx, generics, &parameters[..idx], &parameters[idx])
+        })
+        .collect()
+}
+
+// `let _: Ty = default;` with the earlier parameters it refers to as the inputs, `impl Trait` can't be written there
+fn generate_check(
+    fn_name: &Ident,
+    idx: usize,
+    generics: &Generics,
+    earlier: &[Parameter],
+    parameter: &Parameter,
+) -> Option<TokenStream> {
+    let (_, default) = parameter.default.as_ref()?;
+    let default = default.inner();
+    let ty = &parameter.ty;
+
+    if contains_impl_trait(ty.to_token_stream()) {
+        return None;
+    }
+
+    // only the earlier parameters that the default argument refers to are the inputs
+    let earlier = earlier
+        .iter()
+        .filter(|param| {
+            param
+                .binding_ident()
+                .is_some_and(|ident| parameter.default_refers_to(ident))
+        })
+        .collect::<Vec<_>>();
+
+    let mut tokens = quote!(#ty #default);
+    for param in &earlier {
+        param.ty.to_tokens(&mut tokens);
+    }
+
+    if generics
+        .params
+        .iter()
+        .any(|param| mentions(tokens.clone(), param))
+    {
+        return None;
+    }
+
+    let check = format_ident!("__nade_check_{}_{}", fn_name, idx);
+    let inputs = earlier.iter().map(|param| {
+        let ident = param.binding_ident();
+        let ty = &param.ty;
+        quote!(#ident: #ty)
+    });
+    let span = default.span();
+
+    Some(quote_spanned! {span=>
+        #[doc(hidden)]
+        #[allow(dead_code, unused_variables, clippy::too_many_arguments)]
+        fn #check(#(#inputs),*) {
+            let _: #ty = #default;
+        }
+    })
+}
+
+// The generics of the accessor are the generic parameters mentioned by the default argument and
+// the type, together with the ones their bounds need. They are inferred from the return type,
+// so `None` is returned when a type or const parameter doesn't appear in the type, or when only
+// the type mentions them, like `#[nade("a")] a: T`, which relies on the inference at the call site.
+fn accessor_generics(
+    generics: &Generics,
+    ty: TokenStream,
+    default: TokenStream,
+) -> Option<Generics> {
+    let params = generics.params.iter().collect::<Vec<_>>();
+
+    let in_ty = params
+        .iter()
+        .map(|param| mentions(ty.clone(), param))
+        .collect::<Vec<_>>();
+
+    let mut included = params
+        .iter()
+        .zip(&in_ty)
+        .map(|(param, in_ty)| *in_ty || mentions(default.clone(), param))
+        .collect::<Vec<_>>();
+
+    let default_mentions_generics = params
+        .iter()
+        .any(|param| !is_lifetime(param) && mentions(default.clone(), param));
+
+    if !default_mentions_generics
+        && params
+            .iter()
+            .zip(&in_ty)
+            .any(|(p, t)| *t && !is_lifetime(p))
+    {
+        return None;
+    }
+
+    // `T: Into<U>` needs `U` as well
+    loop {
+        let mut changed = false;
+
+        for idx in 0..params.len() {
+            if !included[idx] {
+                continue;
+            }
+
+            let bounds = bounds_of(params[idx]);
+
+            for (other_idx, other) in params.iter().enumerate() {
+                if !included[other_idx] && mentions(bounds.clone(), other) {
+                    included[other_idx] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    if params
+        .iter()
+        .zip(&included)
+        .zip(&in_ty)
+        .any(|((param, included), in_ty)| *included && !*in_ty && !is_lifetime(param))
+    {
+        return None;
+    }
+
+    let mut accessor_generics = Generics {
+        lt_token: generics.lt_token,
+        params: Punctuated::new(),
+        gt_token: generics.gt_token,
+        where_clause: None,
+    };
+
+    for (param, _) in params
+        .iter()
+        .zip(&included)
+        .filter(|(_, included)| **included)
+    {
+        accessor_generics.params.push((*param).clone());
+    }
+
+    // a predicate is kept when every generic parameter it mentions is kept
+    if let Some(where_clause) = &generics.where_clause {
+        let predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| {
+                params.iter().zip(&included).all(|(param, included)| {
+                    *included || !mentions(predicate.to_token_stream(), param)
+                })
+            })
+            .cloned()
+            .collect::<Punctuated<_, _>>();
+
+        if !predicates.is_empty() {
+            accessor_generics.where_clause = Some(WhereClause {
+                where_token: where_clause.where_token,
+                predicates,
+            });
+        }
+    }
+
+    Some(accessor_generics)
+}
+
+fn is_lifetime(param: &GenericParam) -> bool {
+    matches!(param, GenericParam::Lifetime(_))
+}
+
+fn bounds_of(param: &GenericParam) -> TokenStream {
+    match param {
+        GenericParam::Lifetime(l) => l.bounds.to_token_stream(),
+        GenericParam::Type(t) => t.bounds.to_token_stream(),
+        GenericParam::Const(c) => c.ty.to_token_stream(),
+    }
+}
+
+pub(crate) fn mentions(tokens: TokenStream, param: &GenericParam) -> bool {
+    match param {
+        GenericParam::Lifetime(l) => mentions_lifetime(tokens, &l.lifetime),
+        GenericParam::Type(t) => mentions_ident(tokens, &t.ident),
+        GenericParam::Const(c) => mentions_ident(tokens, &c.ident),
+    }
+}
+
+pub(crate) fn mentions_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(i) => i == *ident,
+        TokenTree::Group(group) => mentions_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+fn mentions_lifetime(tokens: TokenStream, lifetime: &Lifetime) -> bool {
+    let trees = tokens.into_iter().collect::<Vec<_>>();
+
+    trees.iter().enumerate().any(|(idx, tree)| match tree {
+        TokenTree::Punct(p) if p.as_char() == '\'' => {
+            matches!(trees.get(idx + 1), Some(TokenTree::Ident(i)) if *i == lifetime.ident)
+        }
+        TokenTree::Group(group) => mentions_lifetime(group.stream(), lifetime),
+        _ => false,
+    })
+}
+
+fn contains_elided_lifetime(tokens: TokenStream) -> bool {
+    let mut elided = false;
+
+    replace_elided_lifetimes(tokens, &mut || {
+        elided = true;
+        Lifetime::new("'_", Span::call_site())
+    });
+
+    elided
+}
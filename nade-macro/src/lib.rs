@@ -1,53 +1,159 @@
 mod argument;
+mod builder;
+mod default_accessor;
 mod maybe_start_with_dollar;
 mod nade;
+mod nade_enum;
+mod nade_extern;
 mod nade_helper;
+mod nade_impl;
+mod nade_struct;
+mod nade_wrap;
 mod parameter;
+mod parameter_attribute;
 mod parameter_doc;
 mod path_attribute;
 
 use nade_helper::NadeHelper;
+use nade_wrap::Wrap;
 use proc_macro::TokenStream;
-use quote::ToTokens;
-use syn::{parse_macro_input, ItemFn, Path};
+use proc_macro2::Span;
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, spanned::Spanned, ForeignItemFn, Ident, Item, Path};
 
-use crate::maybe_start_with_dollar::StartWithDollar;
+use crate::{maybe_start_with_dollar::StartWithDollar, nade::FnOptions};
 
 #[proc_macro_attribute]
 pub fn nade(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let mut fun = parse_macro_input!(item as ItemFn);
+    let mut item = parse_macro_input!(item as Item);
 
     let mut module_path: Option<StartWithDollar<Path>> = None;
+    let mut fn_options = FnOptions::default();
+    // the options that are only supported on functions
+    let mut fn_only_options: Vec<(Span, &str)> = Vec::new();
+
+    let attr_parser = syn::meta::parser(|meta| {
+        macro_rules! fn_option {
+            ($option:ident) => {{
+                if fn_options.$option {
+                    return Err(meta.error(concat!(
+                        "duplicate `",
+                        stringify!($option),
+                        "` argument"
+                    )));
+                }
+                fn_options.$option = true;
+                fn_only_options.push((meta.path.span(), stringify!($option)));
+            }};
+        }
+
+        // `option = value`
+        macro_rules! fn_value_option {
+            ($option:ident) => {{
+                if fn_options.$option.is_some() {
+                    return Err(meta.error(concat!(
+                        "duplicate `",
+                        stringify!($option),
+                        "` argument"
+                    )));
+                }
+                fn_options.$option = Some(meta.value()?.parse()?);
+                fn_only_options.push((meta.path.span(), stringify!($option)));
+            }};
+        }
 
-    let module_path_parser = syn::meta::parser(|meta| {
         if meta.path.is_ident("module_path") {
             if module_path.is_some() {
                 return Err(meta.error("duplicate `module_path` argument"));
             }
             module_path = Some(meta.value()?.parse()?);
-            Ok(())
+        } else if meta.path.is_ident("builder") {
+            fn_option!(builder);
+        } else if meta.path.is_ident("all_named") {
+            fn_option!(all_named);
+        } else if meta.path.is_ident("reorder") {
+            let value = meta.value()?.parse::<Ident>()?;
+            if value != "allow" {
+                return Err(syn::Error::new(value.span(), "expected `allow`"));
+            }
+            fn_option!(reorder);
+        } else if meta.path.is_ident("positional") {
+            let value = meta.value()?.parse::<Ident>()?;
+            if value != "fill" {
+                return Err(syn::Error::new(value.span(), "expected `fill`"));
+            }
+            fn_option!(positional);
+        } else if meta.path.is_ident("forward") {
+            fn_value_option!(forward);
+        } else if meta.path.is_ident("macro_name") {
+            fn_value_option!(macro_name);
+        } else if meta.path.is_ident("macro_vis") {
+            fn_value_option!(macro_vis);
         } else {
-            Err(meta.error("expected `module_path`"))
+            return Err(meta.error(
+                "expected `module_path`, `builder`, `all_named`, `reorder`, `positional`, \
+                    `forward`, `macro_name` or `macro_vis`",
+            ));
         }
+
+        Ok(())
     });
 
-    parse_macro_input!(attr with module_path_parser);
+    parse_macro_input!(attr with attr_parser);
+
+    let result = match (&mut item, fn_only_options.first()) {
+        (Item::Fn(fun), _) => nade::generate(module_path, fn_options, fun),
+        (_, Some((span, option))) => Err(syn::Error::new(
+            *span,
+            format!("`{}` is only supported on functions", option),
+        )),
+        (Item::Impl(item_impl), None) => nade_impl::generate(module_path, item_impl),
+        (Item::Struct(item_struct), None) => nade_struct::generate(module_path, item_struct),
+        (Item::Enum(item_enum), None) => nade_enum::generate(module_path, item_enum),
+        (Item::ForeignMod(item_foreign_mod), None) => {
+            nade_extern::generate(module_path, item_foreign_mod)
+        }
+        // a foreign function is parsed as verbatim tokens
+        (Item::Verbatim(tokens), None) if syn::parse2::<ForeignItemFn>(tokens.clone()).is_ok() => {
+            Err(syn::Error::new(
+                Span::call_site(),
+                "`#[nade]` on a foreign function must be placed on the enclosing `extern` block",
+            ))
+        }
+        (_, None) => Err(syn::Error::new(
+            Span::call_site(),
+            "`#[nade]` can only be used on functions, structs, enums, `impl` blocks and `extern` blocks",
+        )),
+    };
 
-    nade::generate(module_path, &mut fun)
+    result
         .unwrap_or_else(|e| {
             let mut stream = e.to_compile_error();
-            stream.extend(fun.to_token_stream());
+            stream.extend(item.to_token_stream());
             stream
         })
         .into()
 }
 
+#[proc_macro]
+pub fn wrap(input: TokenStream) -> TokenStream {
+    let wrap = parse_macro_input!(input as Wrap);
+
+    nade_wrap::generate(wrap)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 #[doc(hidden)]
 #[proc_macro]
 pub fn nade_helper(input: TokenStream) -> TokenStream {
     let nade_helper = parse_macro_input!(input as NadeHelper);
 
     nade_helper::generate(nade_helper)
-        .unwrap_or_else(|e| e.to_compile_error())
+        .unwrap_or_else(|e| {
+            // several `compile_error!`s are only valid as statements, the macro is an expression
+            let errors = e.to_compile_error();
+            quote!({ #errors })
+        })
         .into()
 }
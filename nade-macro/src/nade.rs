@@ -1,61 +1,270 @@
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
-    parse_quote, punctuated::Punctuated, spanned::Spanned, AttrStyle, Attribute, Expr, ExprLit,
-    File, FnArg, Ident, Item, ItemFn, Lit, LitStr, Meta, MetaList, MetaNameValue, Pat, PatType,
-    Path, ReturnType, Token, Type,
+    parse_quote, parse_quote_spanned, punctuated::Punctuated, spanned::Spanned, AttrStyle,
+    Attribute, Expr, ExprLit, File, FnArg, Generics, Ident, Item, ItemFn, Lit, LitStr, Meta,
+    MetaNameValue, Pat, PatIdent, PatType, Path, Receiver, ReturnType, Token, Type, Visibility,
 };
 
 use crate::{
+    builder,
+    default_accessor::{self, Holder},
     maybe_start_with_dollar::{MaybeStartWithDollar, StartWithDollar},
     parameter::Parameter,
+    parameter_attribute::{ParameterAttr, PassingMode},
     parameter_doc::ParameterDoc,
     path_attribute::PathAttr,
 };
 
+/// The options of `#[nade(..)]` that are only supported on functions.
+#[derive(Default)]
+pub(crate) struct FnOptions {
+    pub(crate) builder: bool,
+    pub(crate) all_named: bool,
+    // `reorder = allow`
+    pub(crate) reorder: bool,
+    // `positional = fill`
+    pub(crate) positional: bool,
+    // `forward = path::to::function`
+    pub(crate) forward: Option<MaybeStartWithDollar<Path>>,
+    // `macro_name = ident`, the name of the macro instead of the function name
+    pub(crate) macro_name: Option<Ident>,
+    // `macro_vis = pub`, the visibility of the macro instead of the function visibility
+    pub(crate) macro_vis: Option<Visibility>,
+}
+
 pub(crate) fn generate(
     module_path: Option<StartWithDollar<Path>>,
+    fn_options: FnOptions,
     fun: &mut ItemFn,
 ) -> syn::Result<TokenStream> {
-    let PathAttr {
-        macro_v: macro_v_path,
-        nade_helper: nade_helper_path,
-    } = PathAttr::parse_attrs(&mut fun.attrs)?;
+    let FnOptions {
+        builder,
+        all_named,
+        reorder,
+        positional,
+        forward,
+        macro_name,
+        macro_vis,
+    } = fn_options;
+
+    if let Some(FnArg::Receiver(r)) = fun.sig.inputs.first() {
+        return Err(syn::Error::new(
+            r.span(),
+            "`#[nade]` on a method must be placed on the enclosing `impl` block",
+        ));
+    }
 
-    let (parameters, parameter_docs) = extract_parameters_and_docs(&mut fun.sig.inputs)?;
+    // the builder can't inherit the default arguments, it would disagree with the macro
+    if let (true, Some(forward)) = (builder, &forward) {
+        return Err(syn::Error::new(
+            forward.span(),
+            "`forward` cannot be used with `builder`, the builder can't inherit the default arguments",
+        ));
+    }
 
-    let name = &fun.sig.ident;
-    let vis = &fun.vis;
+    let path_attr = PathAttr::parse_attrs(&mut fun.attrs)?;
+
+    let (mut parameters, mut parameter_docs) = extract_parameters_and_docs(&mut fun.sig.inputs)?;
+    mark_generic_parameters(&mut parameters, &fun.sig.generics);
+
+    if all_named {
+        // an explicit `#[nade(positional)]` still takes precedence
+        for parameter in parameters
+            .iter_mut()
+            .filter(|param| param.mode == PassingMode::Any)
+        {
+            parameter.mode = PassingMode::Named;
+        }
+        for parameter_doc in parameter_docs
+            .iter_mut()
+            .filter(|doc| doc.mode == PassingMode::Any)
+        {
+            parameter_doc.mode = PassingMode::Named;
+        }
+    }
 
-    let macro_docs = generate_macro_docs(&fun.attrs, name);
-    let parameter_docs = generate_parameter_docs(parameter_docs);
-    let return_doc = generate_return_doc(&fun.sig.output);
+    let builder_def = if builder {
+        builder::generate(fun, &parameters, &parameter_docs)?
+    } else {
+        quote! {}
+    };
 
+    let name = &fun.sig.ident;
     let module_path = module_path.map(|path| quote!(#path::));
+    let reorder = reorder.then(|| quote!(#[reorder(allow)]));
+    let positional = positional.then(|| quote!(#[positional(fill)]));
+
+    let vis = macro_vis.as_ref().unwrap_or(&fun.vis);
+
+    // the accessors are called by the macro, so they are as visible as it
+    let (default_accessors, default_checks) = default_accessor::generate(
+        name,
+        vis,
+        &fun.sig.generics,
+        Holder::Fn {
+            name: name.clone(),
+            module_path: module_path.as_ref(),
+        },
+        &mut parameters,
+    )?;
+
+    let macro_def = generate_macro(
+        &path_attr,
+        MacroDef {
+            attrs: &fun.attrs,
+            vis,
+            name: macro_name.unwrap_or_else(|| name.clone()),
+            link_doc: format!("Wrapper macro for function [`{}`]({}()).", name, name),
+            parameters,
+            parameter_docs,
+            output: fun.sig.output.clone(),
+            target: quote!(#reorder #positional #module_path #name),
+            forward: forward.map(|path| quote!(#path)),
+        },
+    );
 
     let expand = quote! {
         #[allow(clippy::too_many_arguments)]
         #fun
 
+        #(#default_accessors)*
+
+        #(#default_checks)*
+
+        #macro_def
+
+        #builder_def
+    };
+
+    Ok(expand)
+}
+
+pub(crate) struct MacroDef<'a> {
+    pub(crate) attrs: &'a [Attribute],
+    pub(crate) vis: &'a Visibility,
+    pub(crate) name: Ident,
+    pub(crate) link_doc: String,
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) parameter_docs: Vec<ParameterDoc>,
+    pub(crate) output: ReturnType,
+    pub(crate) target: TokenStream,
+    // the macro of the function whose parameters are inherited
+    pub(crate) forward: Option<TokenStream>,
+}
+
+pub(crate) fn generate_macro(path_attr: &PathAttr, macro_def: MacroDef) -> TokenStream {
+    let PathAttr {
+        macro_v: macro_v_path,
+        nade_helper: nade_helper_path,
+    } = path_attr;
+
+    let MacroDef {
+        attrs,
+        vis,
+        name,
+        link_doc,
+        parameters,
+        parameter_docs,
+        output,
+        target,
+        forward,
+    } = macro_def;
+
+    // the signature in the error notes of `nade_helper`, with the default arguments as written
+    let signature = LitStr::new(
+        &generate_signature(&target, &parameter_docs),
+        Span::call_site(),
+    );
+    let target = quote!(#[signature(#signature)] #target);
+
+    let main_arm = match &forward {
+        Some(forward) => quote! {
+            ($($arguments:tt)*) => {
+                #forward!(
+                    @__nade_forward
+                    ($($arguments)*)
+                    (#(#parameters,)*)
+                    (#target)
+                )
+            };
+        },
+        None => quote! {
+            ($($arguments:tt)*) => {
+                #nade_helper_path::nade_helper!(
+                    ($($arguments)*)
+                    (#(#parameters,)*)
+                    (#target)
+                )
+            };
+        },
+    };
+
+    let macro_docs = generate_macro_docs(attrs, &name, &link_doc);
+    let inherited_from = forward
+        .as_ref()
+        .map(|forward| forward.to_string().replace(' ', ""));
+    let parameter_docs = generate_parameter_docs(parameter_docs, inherited_from.as_deref());
+    let return_doc = generate_return_doc(&output);
+
+    quote! {
         #[#macro_v_path::macro_v(#vis)]
         #macro_docs
         #parameter_docs
         #return_doc
         macro_rules! #name {
-            ($($arguments:tt)*) => {
+            // used by the macros of the functions with `#[nade(forward = ..)]`, passes the
+            // parameters of this macro as the inherited ones
+            (@__nade_forward ($($arguments:tt)*) ($($parameters:tt)*) ($($target:tt)*)) => {
                 #nade_helper_path::nade_helper!(
                     ($($arguments)*)
+                    ($($parameters)*)
+                    ($($target)*)
                     (#(#parameters,)*)
-                    (#module_path #name)
                 )
-            }
+            };
+            #main_arm
         }
-    };
+    }
+}
 
-    Ok(expand)
+// `foo(a: u32 = 42, b: u32)`, or `Foo { a: u32 = 42, b: u32 }` for a struct
+fn generate_signature(target: &TokenStream, parameter_docs: &[ParameterDoc]) -> String {
+    let mut is_struct = false;
+    let mut name = String::new();
+
+    for tree in target.clone() {
+        if let TokenTree::Ident(ident) = tree {
+            is_struct |= ident == "struct";
+            name = ident.to_string();
+        }
+    }
+
+    let params = parameter_docs
+        .iter()
+        .map(|doc| {
+            let pattern = match &doc.name {
+                Some(name) => name.to_string(),
+                None => generate_pretty_pat(&doc.pattern),
+            };
+            let default = match &doc.default {
+                Some(default) => format!(" = {}", generate_pretty_expr(default)),
+                None => String::new(),
+            };
+
+            format!("{}: {}{}", pattern, generate_pretty_ty(&doc.ty), default)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if is_struct {
+        format!("{} {{ {} }}", name, params)
+    } else {
+        format!("{}({})", name, params)
+    }
 }
 
-fn extract_parameters_and_docs(
+pub(crate) fn extract_parameters_and_docs(
     inputs: &mut Punctuated<FnArg, Token![,]>,
 ) -> syn::Result<(Vec<Parameter>, Vec<ParameterDoc>)> {
     let mut parameters = Vec::new();
@@ -63,87 +272,256 @@ fn extract_parameters_and_docs(
 
     for arg in inputs.iter_mut() {
         match arg {
-            FnArg::Receiver(r) => {
-                return Err(syn::Error::new(r.span(), "`self` is not support"));
-            }
-            FnArg::Typed(PatType {
+            FnArg::Receiver(Receiver {
                 attrs,
-                pat,
+                self_token,
                 colon_token,
                 ty,
+                ..
             }) => {
-                let mut nade_attrs = drain_filter(attrs, |attr| attr.path().is_ident("nade"));
-
-                if nade_attrs.len() > 1 {
-                    const MSG: &str =
-                        "the `#[nade(..)]` attribute can only be used once per parameter";
-
-                    if let Some(e) = nade_attrs
-                        .iter()
-                        .skip(1)
-                        .map(|attr| syn::Error::new(attr.span(), MSG))
-                        .reduce(|mut a, b| {
-                            a.combine(b);
-                            a
-                        })
-                    {
-                        return Err(e);
-                    }
+                if let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("nade")) {
+                    return Err(syn::Error::new(
+                        attr.span(),
+                        "the receiver cannot have a default argument",
+                    ));
                 }
 
-                let doc_attrs = drain_filter(attrs, |attr| attr.path().is_ident("doc"));
-
-                let default = match nade_attrs.pop() {
-                    Some(nade_attr) => {
-                        let expr = extract_parameter_default(nade_attr)?;
-                        Some(expr)
-                    }
-                    None => None,
-                };
-
-                let docs = doc_attrs
-                    .into_iter()
-                    .filter_map(|attr| {
-                        if !matches!(attr.style, AttrStyle::Outer) {
-                            return None;
-                        }
-
-                        if let Meta::NameValue(MetaNameValue {
-                            value:
-                                Expr::Lit(ExprLit {
-                                    lit: Lit::Str(s), ..
-                                }),
-                            ..
-                        }) = attr.meta
-                        {
-                            return Some(s);
-                        }
-
-                        None
-                    })
-                    .collect::<Vec<_>>();
+                let docs = collect_docs(attrs);
+                attrs.retain(|attr| !attr.path().is_ident("doc"));
+
+                // `mut self` is matched and documented as `self`
+                let pat: Pat = parse_quote_spanned!(self_token.span=> self);
 
                 parameter_docs.push(ParameterDoc {
-                    pattern: *pat.clone(),
+                    pattern: pat.clone(),
                     ty: *ty.clone(),
                     docs,
-                    default: default.as_ref().map(|d| d.inner().clone()),
+                    default: None,
+                    rest: false,
+                    mode: PassingMode::Any,
+                    aliases: Vec::new(),
+                    name: None,
                 });
 
                 parameters.push(Parameter::new(
+                    pat,
+                    colon_token.unwrap_or_default(),
+                    *ty.clone(),
+                    ParameterAttr::default(),
+                ));
+            }
+            FnArg::Typed(PatType {
+                attrs,
+                pat,
+                colon_token,
+                ty,
+            }) => {
+                let docs = collect_docs(attrs);
+                attrs.retain(|attr| !attr.path().is_ident("doc"));
+
+                let (parameter, parameter_doc) = extract_parameter_and_doc(
+                    attrs,
                     *pat.clone(),
                     *colon_token,
                     *ty.clone(),
-                    default,
-                ));
+                    docs,
+                )?;
+
+                parameter_docs.push(parameter_doc);
+                parameters.push(parameter);
             }
         }
     }
 
+    check_receiver_references(&parameters)?;
+    check_parameters(&parameters)?;
+
     Ok((parameters, parameter_docs))
 }
 
-fn generate_macro_docs<'a>(attrs: &'a [Attribute], name: &'a Ident) -> TokenStream {
+// the receiver is not a parameter that the macro binds, and the default arguments of a method are
+// evaluated in functions without it
+fn check_receiver_references(parameters: &[Parameter]) -> syn::Result<()> {
+    let Some(Pat::Ident(PatIdent {
+        ident: receiver, ..
+    })) = parameters.first().map(|param| &param.pat)
+    else {
+        return Ok(());
+    };
+
+    if receiver != "self" {
+        return Ok(());
+    }
+
+    match parameters
+        .iter()
+        .find(|param| param.default_refers_to(receiver))
+        .and_then(|param| param.default.as_ref())
+    {
+        Some((_, default)) => Err(syn::Error::new(
+            default.span(),
+            "default arguments cannot refer to the receiver",
+        )),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn check_parameters(parameters: &[Parameter]) -> syn::Result<()> {
+    check_rest_parameter(parameters)?;
+    check_parameter_names(parameters)?;
+    check_default_references(parameters)
+}
+
+fn check_rest_parameter(parameters: &[Parameter]) -> syn::Result<()> {
+    match parameters.iter().rev().skip(1).find(|param| param.rest) {
+        Some(param) => Err(syn::Error::new(
+            param.pat.span(),
+            "`#[nade(rest)]` can only be used on the last parameter",
+        )),
+        None => Ok(()),
+    }
+}
+
+// a `name` or an alias must not be used by another parameter
+fn check_parameter_names(parameters: &[Parameter]) -> syn::Result<()> {
+    for (idx, param) in parameters.iter().enumerate() {
+        for alias in param.name.iter().chain(&param.aliases) {
+            let alias_pat = Pat::Ident(PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: alias.clone(),
+                subpat: None,
+            });
+
+            let conflict = parameters
+                .iter()
+                .enumerate()
+                .any(|(other_idx, other)| other_idx != idx && other.is_named_by(&alias_pat));
+
+            if conflict {
+                return Err(syn::Error::new(
+                    alias.span(),
+                    format!("`{}` is already used by another parameter", alias),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// a default argument can only refer to the parameters declared before it
+fn check_default_references(parameters: &[Parameter]) -> syn::Result<()> {
+    for (idx, param) in parameters.iter().enumerate() {
+        let Some((_, default)) = &param.default else {
+            continue;
+        };
+
+        if let Some(ident) = parameters[idx..]
+            .iter()
+            .filter_map(Parameter::binding_ident)
+            .find(|ident| param.default_refers_to(ident))
+        {
+            return Err(syn::Error::new(
+                default.span(),
+                format!(
+                    "the default argument cannot refer to parameter `{}`, \
+                        only the parameters declared before it can be referred to",
+                    ident
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// the types of these parameters are not written where the macro is called, where the generic
+// parameters and `Self` of the definition don't exist
+pub(crate) fn mark_generic_parameters(parameters: &mut [Parameter], generics: &Generics) {
+    for parameter in parameters {
+        let ty = parameter.ty.to_token_stream();
+
+        parameter.generic |= default_accessor::mentions_ident(ty.clone(), &format_ident!("Self"))
+            || generics
+                .params
+                .iter()
+                .any(|param| default_accessor::mentions(ty.clone(), param));
+    }
+}
+
+pub(crate) fn extract_parameter_and_doc(
+    attrs: &mut Vec<Attribute>,
+    pat: Pat,
+    colon_token: Token![:],
+    ty: Type,
+    docs: Vec<LitStr>,
+) -> syn::Result<(Parameter, ParameterDoc)> {
+    let mut nade_attrs = drain_filter(attrs, |attr| attr.path().is_ident("nade"));
+
+    if nade_attrs.len() > 1 {
+        const MSG: &str = "the `#[nade(..)]` attribute can only be used once per parameter";
+
+        if let Some(e) = nade_attrs
+            .iter()
+            .skip(1)
+            .map(|attr| syn::Error::new(attr.span(), MSG))
+            .reduce(|mut a, b| {
+                a.combine(b);
+                a
+            })
+        {
+            return Err(e);
+        }
+    }
+
+    let parameter_attr = match nade_attrs.pop() {
+        Some(nade_attr) => ParameterAttr::parse_attr(nade_attr)?,
+        None => ParameterAttr::default(),
+    };
+
+    let parameter_doc = ParameterDoc {
+        pattern: pat.clone(),
+        ty: ty.clone(),
+        docs,
+        default: parameter_attr.default.as_ref().map(|d| d.inner().clone()),
+        rest: parameter_attr.rest,
+        mode: parameter_attr.mode,
+        aliases: parameter_attr.aliases.clone(),
+        name: parameter_attr.name.clone(),
+    };
+
+    let parameter = Parameter::new(pat, colon_token, ty, parameter_attr);
+
+    Ok((parameter, parameter_doc))
+}
+
+pub(crate) fn collect_docs(attrs: &[Attribute]) -> Vec<LitStr> {
+    attrs
+        .iter()
+        .filter_map(|attr| {
+            if !matches!(attr.style, AttrStyle::Outer) || !attr.path().is_ident("doc") {
+                return None;
+            }
+
+            if let Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(s), ..
+                    }),
+                ..
+            }) = &attr.meta
+            {
+                return Some(s.clone());
+            }
+
+            None
+        })
+        .collect()
+}
+
+fn generate_macro_docs<'a>(attrs: &'a [Attribute], name: &'a Ident, link_doc: &str) -> TokenStream {
     let mut has_doc_comment = false;
 
     let fn_docs = attrs
@@ -164,9 +542,7 @@ fn generate_macro_docs<'a>(attrs: &'a [Attribute], name: &'a Ident) -> TokenStre
         quote! {}
     };
 
-    let link_doc = format!("Wrapper macro for function [`{}`]({}()).", name, name);
-
-    let link_to_fn = LitStr::new(&link_doc, name.span());
+    let link_to_fn = LitStr::new(link_doc, name.span());
 
     quote! {
         #(#fn_docs)*
@@ -175,36 +551,80 @@ fn generate_macro_docs<'a>(attrs: &'a [Attribute], name: &'a Ident) -> TokenStre
     }
 }
 
-fn generate_parameter_docs(docs: Vec<ParameterDoc>) -> TokenStream {
+// `inherited_from` is the function whose default arguments are inherited by `forward = ..`
+fn generate_parameter_docs(docs: Vec<ParameterDoc>, inherited_from: Option<&str>) -> TokenStream {
     if docs.is_empty() {
         return quote! {};
     }
 
-    let docs = docs.into_iter().map(generate_single_parameter_doc);
+    let docs = docs
+        .into_iter()
+        .map(|doc| generate_single_parameter_doc(doc, inherited_from));
     quote! {
         #[doc = "## Parameters"]
         #(#docs)*
     }
 }
 
-fn generate_single_parameter_doc(parameter_doc: ParameterDoc) -> TokenStream {
+fn generate_single_parameter_doc(
+    parameter_doc: ParameterDoc,
+    inherited_from: Option<&str>,
+) -> TokenStream {
     let ParameterDoc {
         pattern,
         ty,
         docs,
         default,
+        rest,
+        mode,
+        aliases,
+        name,
     } = parameter_doc;
 
-    let pretty_pattern = generate_pretty_pat(&pattern);
+    // a complex pattern is documented by the name used at call sites
+    let pretty_pattern = match &name {
+        Some(name) => name.to_string(),
+        None => generate_pretty_pat(&pattern),
+    };
     let pretty_ty = generate_pretty_ty(&ty);
 
-    let pretty_default = default
-        .map(|expr| format!(" = {}", generate_pretty_expr(&expr)))
-        .unwrap_or_default();
+    // only the parameters with a binding identifier inherit the default arguments
+    let inherits = name.is_some() || matches!(&pattern, Pat::Ident(PatIdent { subpat: None, .. }));
+
+    let pretty_default = match (default, inherited_from) {
+        (Some(expr), _) => format!(" = {}", generate_pretty_expr(&expr)),
+        (None, Some(inherited_from)) if inherits => {
+            format!(" *(default inherited from `{}`, if any)*", inherited_from)
+        }
+        (None, _) => String::new(),
+    };
+
+    let rest = if rest {
+        " *(collects the remaining arguments)*"
+    } else {
+        ""
+    };
+
+    let mode = match mode {
+        PassingMode::Any => "",
+        PassingMode::Named => " *(named only)*",
+        PassingMode::Positional => " *(positional only)*",
+    };
+
+    let aliases = if aliases.is_empty() {
+        String::new()
+    } else {
+        let aliases = aliases
+            .iter()
+            .map(|alias| format!("`{}`", alias))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(" *(alias: {})*", aliases)
+    };
 
     let parameter_define = format!(
-        "- **{}** : [`{}`]{}",
-        pretty_pattern, pretty_ty, pretty_default
+        "- **{}** : [`{}`]{}{}{}{}",
+        pretty_pattern, pretty_ty, pretty_default, rest, mode, aliases
     );
 
     let parameter_define = LitStr::new(&parameter_define, pattern.span());
@@ -240,34 +660,45 @@ fn generate_return_doc(output: &ReturnType) -> TokenStream {
     }
 }
 
-fn extract_parameter_default(attr: Attribute) -> syn::Result<MaybeStartWithDollar<Expr>> {
-    let default = match attr.meta {
-        Meta::Path(_) => Ok(MaybeStartWithDollar::Normal(parse_quote!(
-            ::core::default::Default::default()
-        ))),
-        Meta::List(MetaList { tokens, .. }) => syn::parse2(tokens),
-        Meta::NameValue(a) => Err(syn::Error::new(
-            a.span(),
-            "the `#[nade]` attribute does not support `#[nade = ..]`",
-        )),
-    }?;
-
-    if let Expr::Assign(_) = default.inner() {
+pub(crate) fn parse_macro_name(nade_attrs: &[Attribute], kind: &str) -> syn::Result<Option<Ident>> {
+    if let Some(attr) = nade_attrs.get(1) {
         return Err(syn::Error::new(
-            default.span(),
-            "assignment expression is not supported \
-                because it is not possible to distinguish \
-                whether it is a named general expression \
-                or a non-named assignment expression.",
+            attr.span(),
+            format!(
+                "the `#[nade(..)]` attribute can only be used once per {}",
+                kind
+            ),
         ));
     }
 
-    Ok(default)
+    let Some(attr) = nade_attrs.first() else {
+        return Ok(None);
+    };
+
+    if let Meta::Path(_) = attr.meta {
+        return Ok(None);
+    }
+
+    let mut macro_name: Option<Ident> = None;
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("macro_name") {
+            if macro_name.is_some() {
+                return Err(meta.error("duplicate `macro_name` argument"));
+            }
+            macro_name = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `macro_name`"))
+        }
+    })?;
+
+    Ok(macro_name)
 }
 
 // implemented manually because Vec::drain_filter is nightly only
 // follows std recommended parallel
-fn drain_filter<T, F>(vec: &mut Vec<T>, mut predicate: F) -> Vec<T>
+pub(crate) fn drain_filter<T, F>(vec: &mut Vec<T>, mut predicate: F) -> Vec<T>
 where
     F: FnMut(&mut T) -> bool,
 {
@@ -291,7 +722,7 @@ fn item_to_file(item: Item) -> File {
     }
 }
 
-fn generate_pretty_pat(pat: &Pat) -> String {
+pub(crate) fn generate_pretty_pat(pat: &Pat) -> String {
     let pat_item: Item = parse_quote! {
         fn a() {
             let #pat = todo!();
@@ -306,7 +737,12 @@ fn generate_pretty_pat(pat: &Pat) -> String {
     pretty_pat.to_string()
 }
 
-fn generate_pretty_ty(ty: &Type) -> String {
+pub(crate) fn generate_pretty_ty(ty: &Type) -> String {
+    // the `...` of a variadic parameter is not a type
+    if let Type::Verbatim(tokens) = ty {
+        return tokens.to_string();
+    }
+
     let type_item: Item = parse_quote! {
         type SomeType = #ty;
     };
@@ -319,7 +755,7 @@ fn generate_pretty_ty(ty: &Type) -> String {
     pretty_ty.to_string()
 }
 
-fn generate_pretty_expr(expr: &Expr) -> String {
+pub(crate) fn generate_pretty_expr(expr: &Expr) -> String {
     let expr_item: Item = parse_quote! {
         fn a() {
             let _ = #expr;
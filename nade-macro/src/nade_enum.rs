@@ -0,0 +1,103 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Fields, Ident, ItemEnum, Path};
+
+use crate::{
+    maybe_start_with_dollar::StartWithDollar,
+    nade::{drain_filter, generate_macro, mark_generic_parameters, parse_macro_name, MacroDef},
+    nade_struct::extract_fields_parameters_and_docs,
+    path_attribute::PathAttr,
+};
+
+pub(crate) fn generate(
+    module_path: Option<StartWithDollar<Path>>,
+    item_enum: &mut ItemEnum,
+) -> syn::Result<TokenStream> {
+    let path_attr = PathAttr::parse_attrs(&mut item_enum.attrs)?;
+
+    let name = &item_enum.ident;
+    let (_, ty_generics, _) = item_enum.generics.split_for_impl();
+    let module_path = module_path.map(|path| quote!(#path::));
+
+    let mut macro_defs = Vec::new();
+
+    for variant in item_enum.variants.iter_mut() {
+        let nade_attrs = drain_filter(&mut variant.attrs, |attr| attr.path().is_ident("nade"));
+
+        let struct_token = match &variant.fields {
+            Fields::Named(_) => quote!(struct),
+            Fields::Unnamed(_) => quote!(),
+            Fields::Unit => continue,
+        };
+
+        let macro_name = parse_macro_name(&nade_attrs, "variant")?;
+
+        let (mut parameters, parameter_docs) =
+            extract_fields_parameters_and_docs(&mut variant.fields)?;
+        mark_generic_parameters(&mut parameters, &item_enum.generics);
+
+        let variant_name = &variant.ident;
+
+        let macro_def = generate_macro(
+            &path_attr,
+            MacroDef {
+                attrs: &variant.attrs,
+                vis: &item_enum.vis,
+                name: macro_name.unwrap_or_else(|| generate_macro_name(name, variant_name)),
+                link_doc: format!(
+                    "Construction macro for variant [`{}::{}`].",
+                    name, variant_name
+                ),
+                parameters,
+                parameter_docs,
+                output: parse_quote!(-> #name #ty_generics),
+                target: quote!(#struct_token #module_path #name::#variant_name),
+                forward: None,
+            },
+        );
+
+        macro_defs.push(macro_def);
+    }
+
+    let expand = quote! {
+        #item_enum
+
+        #(#macro_defs)*
+    };
+
+    Ok(expand)
+}
+
+// `Event::Click` => `event_click`
+fn generate_macro_name(enum_name: &Ident, variant_name: &Ident) -> Ident {
+    format_ident!(
+        "{}_{}",
+        to_snake_case(&enum_name.to_string()),
+        to_snake_case(&variant_name.to_string()),
+        span = variant_name.span()
+    )
+}
+
+// a run of uppercase letters is one word, `HTTPError` => `http_error`
+fn to_snake_case(s: &str) -> String {
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(s.len());
+
+    for (idx, ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() {
+            let after_lower = idx > 0 && !chars[idx - 1].is_uppercase() && chars[idx - 1] != '_';
+            let ends_run = idx > 0
+                && chars[idx - 1].is_uppercase()
+                && chars.get(idx + 1).is_some_and(|next| next.is_lowercase());
+
+            if after_lower || ends_run {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(*ch);
+        }
+    }
+
+    snake
+}
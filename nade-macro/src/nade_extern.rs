@@ -0,0 +1,152 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, spanned::Spanned, FnArg, ForeignItem, ForeignItemFn, Generics, ItemForeignMod,
+    Path, Type, Variadic,
+};
+
+use crate::{
+    default_accessor::{self, Holder},
+    maybe_start_with_dollar::StartWithDollar,
+    nade::{drain_filter, extract_parameters_and_docs, generate_macro, parse_macro_name, MacroDef},
+    parameter::Parameter,
+    parameter_attribute::{ParameterAttr, PassingMode},
+    parameter_doc::ParameterDoc,
+    path_attribute::PathAttr,
+};
+
+pub(crate) fn generate(
+    module_path: Option<StartWithDollar<Path>>,
+    item_foreign_mod: &mut ItemForeignMod,
+) -> syn::Result<TokenStream> {
+    let path_attr = PathAttr::parse_attrs(&mut item_foreign_mod.attrs)?;
+
+    let module_path = module_path.map(|path| quote!(#path::));
+
+    let mut macro_defs = Vec::new();
+    let mut default_accessors = Vec::new();
+
+    for item in item_foreign_mod.items.iter_mut() {
+        let ForeignItem::Fn(fun) = item else {
+            continue;
+        };
+
+        let nade_attrs = drain_filter(&mut fun.attrs, |attr| attr.path().is_ident("nade"));
+
+        if nade_attrs.is_empty() && !has_nade_parameters(fun) {
+            continue;
+        }
+
+        let macro_name = parse_macro_name(&nade_attrs, "function")?;
+
+        let (mut parameters, mut parameter_docs) =
+            extract_parameters_and_docs(&mut fun.sig.inputs)?;
+
+        if let Some(variadic) = &fun.sig.variadic {
+            let (parameter, parameter_doc) = variadic_parameter_and_doc(variadic)?;
+            parameters.push(parameter);
+            parameter_docs.push(parameter_doc);
+        }
+
+        let name = &fun.sig.ident;
+
+        // foreign functions can't be generic
+        let (accessors, checks) = default_accessor::generate(
+            name,
+            &fun.vis,
+            &Generics::default(),
+            Holder::Fn {
+                name: name.clone(),
+                module_path: module_path.as_ref(),
+            },
+            &mut parameters,
+        )?;
+        default_accessors.extend(accessors);
+        default_accessors.extend(checks);
+
+        let macro_def = generate_macro(
+            &path_attr,
+            MacroDef {
+                attrs: &fun.attrs,
+                vis: &fun.vis,
+                name: macro_name.unwrap_or_else(|| name.clone()),
+                link_doc: format!(
+                    "Wrapper macro for foreign function [`{}`]({}()).",
+                    name, name
+                ),
+                parameters,
+                parameter_docs,
+                output: fun.sig.output.clone(),
+                target: quote!(unsafe #module_path #name),
+                forward: None,
+            },
+        );
+
+        macro_defs.push(macro_def);
+    }
+
+    let expand = quote! {
+        #item_foreign_mod
+
+        #(#default_accessors)*
+
+        #(#macro_defs)*
+    };
+
+    Ok(expand)
+}
+
+// `...` collects the remaining positional arguments, `args: ...` can also be passed by name
+fn variadic_parameter_and_doc(variadic: &Variadic) -> syn::Result<(Parameter, ParameterDoc)> {
+    if let Some(attr) = variadic
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("nade"))
+    {
+        return Err(syn::Error::new(
+            attr.span(),
+            "the variadic parameter cannot have a default argument",
+        ));
+    }
+
+    let (pat, colon_token, mode) = match &variadic.pat {
+        Some((pat, colon_token)) => (*pat.clone(), *colon_token, PassingMode::Any),
+        None => (parse_quote!(_), Default::default(), PassingMode::Positional),
+    };
+    let ty = Type::Verbatim(variadic.dots.to_token_stream());
+
+    let mut parameter = Parameter::new(
+        pat.clone(),
+        colon_token,
+        ty.clone(),
+        ParameterAttr {
+            rest: true,
+            mode,
+            ..Default::default()
+        },
+    );
+    parameter.variadic = true;
+
+    let parameter_doc = ParameterDoc {
+        pattern: pat,
+        ty,
+        docs: Vec::new(),
+        default: None,
+        rest: true,
+        mode,
+        aliases: Vec::new(),
+        name: None,
+    };
+
+    Ok((parameter, parameter_doc))
+}
+
+fn has_nade_parameters(fun: &ForeignItemFn) -> bool {
+    fun.sig.inputs.iter().any(|arg| match arg {
+        FnArg::Typed(pat_type) => pat_type
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("nade")),
+        FnArg::Receiver(_) => false,
+    })
+}
@@ -1,40 +1,108 @@
-use proc_macro2::{Span, TokenStream};
-use quote::{quote, ToTokens};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
-    Expr, Path, Token,
+    Attribute, Expr, ExprField, ExprGroup, ExprParen, ExprPath, ExprReference, Ident, Lifetime,
+    LitStr, Pat, PatIdent, Path, Token, Type, TypePath, TypeReference,
 };
 
 use crate::{
-    argument::Argument, maybe_start_with_dollar::MaybeStartWithDollar, parameter::Parameter,
+    argument::Argument,
+    builder::replace_elided_lifetimes,
+    maybe_start_with_dollar::MaybeStartWithDollar,
+    nade::{generate_pretty_expr, generate_pretty_pat, generate_pretty_ty},
+    parameter::Parameter,
+    parameter_attribute::PassingMode,
 };
 
 pub(crate) struct NadeHelper {
     arguments: Punctuated<Argument, Token![,]>,
     parameters: Punctuated<Parameter, Token![,]>,
-    fn_path: MaybeStartWithDollar<Path>,
+    // `#[reorder(allow)]` before the target
+    reorder_allowed: bool,
+    // `#[positional(fill)]` before the target
+    positional_fill: bool,
+    // `#[signature("..")]` before the target, the signature with the default arguments as written
+    signature: Option<LitStr>,
+    target: Target,
+    // `#[nade(forward = ..)]`, the parameters that the defaults are inherited from
+    inherited: Punctuated<Parameter, Token![,]>,
+}
+
+enum Target {
+    Fn(MaybeStartWithDollar<Path>),
+    // a foreign function, called in an `unsafe` block
+    UnsafeFn(MaybeStartWithDollar<Path>),
+    Struct(MaybeStartWithDollar<Path>),
+}
+
+impl Parse for Target {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(if input.peek(Token![struct]) {
+            input.parse::<Token![struct]>()?;
+            Target::Struct(input.parse()?)
+        } else if input.peek(Token![unsafe]) {
+            input.parse::<Token![unsafe]>()?;
+            Target::UnsafeFn(input.parse()?)
+        } else {
+            Target::Fn(input.parse()?)
+        })
+    }
 }
 
 impl Parse for NadeHelper {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let arguments_paren;
         let parameters_paren;
-        let fn_path_paren;
+        let target_paren;
         parenthesized!(arguments_paren in input);
         parenthesized!(parameters_paren in input);
-        parenthesized!(fn_path_paren in input);
+        parenthesized!(target_paren in input);
 
         let arguments = arguments_paren.parse_terminated(Argument::parse, Token![,])?;
         let parameters = parameters_paren.parse_terminated(Parameter::parse, Token![,])?;
-        let fn_path = fn_path_paren.parse::<MaybeStartWithDollar<Path>>()?;
+
+        let mut reorder_allowed = false;
+        let mut positional_fill = false;
+        let mut signature = None;
+
+        for attr in target_paren.call(Attribute::parse_outer)? {
+            if attr.path().is_ident("reorder") && attr.parse_args::<Ident>()? == "allow" {
+                reorder_allowed = true;
+            } else if attr.path().is_ident("positional") && attr.parse_args::<Ident>()? == "fill" {
+                positional_fill = true;
+            } else if attr.path().is_ident("signature") {
+                signature = Some(attr.parse_args::<LitStr>()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected `#[reorder(allow)]`, `#[positional(fill)]` or `#[signature(..)]`",
+                ));
+            }
+        }
+
+        let target = target_paren.parse::<Target>()?;
+
+        // the optional fourth group, the parameters of the function that is forwarded to
+        let inherited = if input.is_empty() {
+            Punctuated::new()
+        } else {
+            let inherited_paren;
+            parenthesized!(inherited_paren in input);
+            inherited_paren.parse_terminated(Parameter::parse, Token![,])?
+        };
 
         Ok(NadeHelper {
             arguments,
             parameters,
-            fn_path,
+            reorder_allowed,
+            positional_fill,
+            signature,
+            target,
+            inherited,
         })
     }
 }
@@ -43,89 +111,629 @@ pub(crate) fn generate(nade_helper: NadeHelper) -> syn::Result<TokenStream> {
     let NadeHelper {
         arguments,
         parameters,
-        fn_path,
+        reorder_allowed,
+        positional_fill,
+        signature,
+        target,
+        inherited,
     } = nade_helper;
 
-    let args_len = arguments.len();
-    let params_len = parameters.len();
+    let parameters = inherit_defaults(parameters, inherited);
 
-    let mut fn_args = Vec::with_capacity(params_len);
-    let mut matched_args_indexes: Vec<usize> = Vec::with_capacity(args_len);
+    // without the signature written by `#[nade]`, it is the one that `nade_helper` sees
+    let signature = match signature {
+        Some(signature) => signature.value(),
+        None => generate_signature(&target, &parameters),
+    };
 
-    for (param_idx, param) in parameters.iter().enumerate() {
-        let arg = get_single_argument(&mut matched_args_indexes, param_idx, param, &arguments)?;
-        fn_args.push(arg);
-    }
+    let positions = positional_positions(&parameters, &arguments, positional_fill);
+
+    // the arguments of an `unsafe` call are evaluated before the `unsafe` block, so that it
+    // doesn't cover them
+    let unsafe_call = matches!(target, Target::UnsafeFn(_));
 
-    if let Some(e) = arguments
+    let values = arguments
         .iter()
-        .enumerate()
-        .filter_map(|(idx, arg)| {
-            if matched_args_indexes.contains(&idx) {
-                None
-            } else {
-                Some(arg)
+        .map(|arg| arg.value().map(ToTokens::to_token_stream))
+        .collect::<Vec<_>>();
+
+    // the errors of the spread argument are reported together with the others
+    let (spread, mut errors) = check_spread_argument(&parameters, &arguments);
+    let spread_binding = format_ident!("__nade_spread", span = Span::mixed_site());
+
+    // a place expression is not bound, so that only the fields passed are moved out of it, like
+    // the struct update syntax, other expressions, and any of an `unsafe` call, are bound and
+    // evaluated once
+    let spread_expr = match spread {
+        Some(Argument::Spread { expr, .. }) => Some(expr),
+        _ => None,
+    };
+    let bound_spread_expr = spread_expr.filter(|expr| unsafe_call || !is_place_expression(expr));
+    let spread_base = match bound_spread_expr {
+        Some(_) => spread_binding.to_token_stream(),
+        None => spread_expr.to_token_stream(),
+    };
+
+    // the parameters without positional-only ones that the spread argument passes its fields to
+    let spread_fields = parameters
+        .iter()
+        .map(|param| match (spread, param.binding_ident()) {
+            (Some(spread), Some(ident))
+                if param.mode != PassingMode::Positional && spread.spreads(ident) =>
+            {
+                Some(quote!(#spread_base.#ident))
             }
+            _ => None,
         })
+        .collect::<Vec<_>>();
+
+    let Matched {
+        mut fn_args,
+        defaulted,
+        args_indexes: matched_args_indexes,
+    } = match match_arguments(&parameters, &arguments, &positions, &values, &spread_fields) {
+        Ok(matched) if errors.is_empty() => matched,
+        matched => {
+            errors.extend(matched.err());
+            let e = combine_errors(errors).unwrap();
+            return Err(with_signature_note(e, &signature));
+        }
+    };
+
+    // Only the arguments with side effects can tell the order they are evaluated in. When they are
+    // passed in a different order than the parameters, they are bound to temporaries in the order
+    // they are written, the spread argument is evaluated last like the struct update syntax. The
+    // other arguments stay in the call, so that they keep the expected types of the parameters.
+    // Only the literals stay in an `unsafe` call, all the other arguments are bound.
+    let effectful = arguments
+        .iter()
         .map(|arg| {
-            syn::Error::new(
-                arg.span(),
-                format!(
-                    "argument `{}` is not matched by any parameters",
-                    arg.to_token_stream()
-                ),
-            )
-        })
-        .reduce(|mut a, b| {
-            a.combine(b);
-            a
+            arg.value().is_some_and(|value| match value {
+                Expr::Lit(_) => false,
+                _ if unsafe_call => true,
+                value => !is_inert(value),
+            })
         })
-    {
-        return Err(e);
+        .collect::<Vec<_>>();
+    let effectful_indexes = matched_args_indexes
+        .iter()
+        .filter(|idx| effectful[**idx])
+        .collect::<Vec<_>>();
+
+    let temporaries = (unsafe_call && effectful.contains(&true)
+        || !reorder_allowed
+            && (bound_spread_expr.is_some() && effectful.contains(&true)
+                || effectful_indexes
+                    .windows(2)
+                    .any(|indexes| indexes[0] > indexes[1])))
+    .then(|| {
+        effectful
+            .iter()
+            .enumerate()
+            .map(|(idx, effectful)| {
+                effectful.then(|| format_ident!("__nade_value{}", idx, span = Span::mixed_site()))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    if let Some(temporaries) = &temporaries {
+        let temporaries = temporaries
+            .iter()
+            .zip(&values)
+            .map(|(temporary, value)| match temporary {
+                Some(temporary) => Some(temporary.to_token_stream()),
+                None => value.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        fn_args = match_arguments(
+            &parameters,
+            &arguments,
+            &positions,
+            &temporaries,
+            &spread_fields,
+        )?
+        .fn_args;
     }
 
-    let expand = quote! {
-        #fn_path(#(#fn_args,)*)
+    let bindings = bindings(&parameters, &defaulted, &fn_args);
+
+    let mut expand = if bindings.iter().all(Option::is_none) {
+        generate_call(target, &parameters, &fn_args)
+    } else {
+        let call_args = bindings
+            .iter()
+            .zip(&fn_args)
+            .map(|(binding, arg)| match binding {
+                Some(Binding { ident, .. }) => ident.to_token_stream(),
+                None => arg.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        bindings.iter().zip(fn_args).rev().fold(
+            generate_call(target, &parameters, &call_args),
+            |expand, (binding, arg)| match binding {
+                Some(Binding {
+                    ident,
+                    ty: Some(ty),
+                }) => {
+                    let bind = typed_binding(ty);
+                    quote! {
+                        match (#bind(#arg),) {
+                            (#ident,) => #expand,
+                        }
+                    }
+                }
+                Some(Binding { ident, ty: None }) => quote! {
+                    match (#arg,) {
+                        (#ident,) => #expand,
+                    }
+                },
+                None => expand,
+            },
+        )
     };
 
+    if let Some(temporaries) = temporaries {
+        // the placeholders, the spread argument and the arguments without side effects are not bound
+        let values = values
+            .iter()
+            .zip(&effectful)
+            .filter_map(|(value, effectful)| value.as_ref().filter(|_| *effectful));
+        let temporaries = temporaries.iter().flatten();
+        let spread_expr = bound_spread_expr.iter();
+        let spread_bindings = spread_expr.clone().map(|_| &spread_binding);
+
+        expand = quote! {
+            match (#(#values,)* #(#spread_expr,)*) {
+                (#(#temporaries,)* #(#spread_bindings,)*) => #expand,
+            }
+        };
+    } else if let Some(spread_expr) = bound_spread_expr {
+        expand = quote! {
+            match (#spread_expr,) {
+                (#spread_binding,) => #expand,
+            }
+        };
+    }
+
     Ok(expand)
 }
 
-fn get_single_argument<'a>(
+// an argument bound before the call, with the type of the parameter when it can be written
+struct Binding<'a> {
+    ident: Ident,
+    ty: Option<&'a Type>,
+}
+
+// When a default argument that is used refers to earlier parameters, these parameters and the
+// default argument are bound in parameter order by nested `match`es, which keep the temporaries
+// alive like a plain call. The parameters that are referred to are bound with their types, so that
+// the default argument sees the same values as the function, like a closure whose parameter types
+// are inferred from the type. The arguments before them with side effects are bound as well, so
+// that they are still evaluated in parameter order, and the others stay in the call.
+fn bindings<'a>(
+    parameters: &'a Punctuated<Parameter, Token![,]>,
+    defaulted: &[bool],
+    fn_args: &[TokenStream],
+) -> Vec<Option<Binding<'a>>> {
+    let referring = parameters
+        .iter()
+        .zip(defaulted)
+        .map(|(param, defaulted)| {
+            *defaulted
+                && parameters
+                    .iter()
+                    .filter_map(Parameter::binding_ident)
+                    .any(|ident| param.default_refers_to(ident))
+        })
+        .collect::<Vec<_>>();
+
+    let referred = parameters
+        .iter()
+        .map(|param| {
+            param.binding_ident().is_some_and(|ident| {
+                parameters
+                    .iter()
+                    .zip(&referring)
+                    .any(|(other, referring)| *referring && other.default_refers_to(ident))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let last = (0..parameters.len())
+        .rev()
+        .find(|idx| referring[*idx] || referred[*idx]);
+
+    parameters
+        .iter()
+        .zip(fn_args)
+        .enumerate()
+        .map(|(idx, (param, arg))| match param.binding_ident() {
+            // the variadic arguments are passed as they are
+            _ if param.variadic || Some(idx) > last => None,
+            // the same hygiene as the default arguments that refer to it
+            Some(ident) if referred[idx] => {
+                let mut ident = ident.clone();
+                ident.set_span(Span::mixed_site().located_at(ident.span()));
+                Some(Binding {
+                    ident,
+                    ty: (!param.generic).then_some(&param.ty),
+                })
+            }
+            _ if referring[idx] || !syn::parse2(arg.clone()).is_ok_and(|arg| is_inert(&arg)) => {
+                Some(Binding {
+                    ident: format_ident!("__nade_arg{}", idx, span = Span::mixed_site()),
+                    ty: None,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// `{ fn bind(value: Ty) -> Ty { value } bind }`, passing the argument to it gives the argument the
+// type of the parameter like the call does, `impl Trait`s become generic parameters and elided
+// lifetimes named ones
+fn typed_binding(ty: &Type) -> TokenStream {
+    let mut lifetimes = Vec::new();
+    let ty = replace_elided_lifetimes(ty.to_token_stream(), &mut || {
+        let lifetime = Lifetime::new(&format!("'__nade{}", lifetimes.len()), Span::mixed_site());
+        lifetimes.push(lifetime.clone());
+        lifetime
+    });
+
+    let mut bounds = Vec::new();
+    let ty = replace_impl_traits(ty, &mut bounds);
+    let impl_params = bounds.iter().enumerate().map(|(idx, bounds)| {
+        let ident = format_ident!("__NadeImpl{}", idx, span = Span::mixed_site());
+        quote!(#ident: #bounds)
+    });
+
+    let bind = format_ident!("__nade_bind", span = Span::mixed_site());
+
+    quote! {
+        {
+            fn #bind<#(#lifetimes,)* #(#impl_params,)*>(value: #ty) -> #ty {
+                value
+            }
+            #bind
+        }
+    }
+}
+
+// `impl A + B` is replaced with `__NadeImpl0` and so on, `bounds` collects `A + B`
+fn replace_impl_traits(tokens: TokenStream, bounds: &mut Vec<TokenStream>) -> TokenStream {
+    let mut trees = tokens.into_iter().peekable();
+    let mut replaced = TokenStream::new();
+
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Ident(ident) if ident == "impl" => {
+                let mut bound = TokenStream::new();
+                let mut depth = 0usize;
+                let mut after_minus = false;
+
+                // the bounds end at a `,` or the `>` that closes the enclosing generic arguments,
+                // the `>` of `->` and the ones of nested generic arguments are part of them
+                while let Some(tree) = trees.peek() {
+                    match tree {
+                        TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => break,
+                        TokenTree::Punct(p) if p.as_char() == '>' && !after_minus => {
+                            if depth == 0 {
+                                break;
+                            }
+                            depth -= 1;
+                        }
+                        TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+                        _ => {}
+                    }
+
+                    after_minus = matches!(tree, TokenTree::Punct(p) if p.as_char() == '-');
+                    bound.extend(trees.next());
+                }
+
+                format_ident!("__NadeImpl{}", bounds.len(), span = Span::mixed_site())
+                    .to_tokens(&mut replaced);
+                bounds.push(bound);
+            }
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    replace_impl_traits(group.stream(), bounds),
+                );
+                new_group.set_span(group.span());
+                new_group.to_tokens(&mut replaced);
+            }
+            tree => tree.to_tokens(&mut replaced),
+        }
+    }
+
+    replaced
+}
+
+// a parameter without a default argument inherits the one of the parameter with the same name
+fn inherit_defaults(
+    mut parameters: Punctuated<Parameter, Token![,]>,
+    inherited: Punctuated<Parameter, Token![,]>,
+) -> Punctuated<Parameter, Token![,]> {
+    for inherited in inherited {
+        let Some(ident) = inherited.binding_ident() else {
+            continue;
+        };
+
+        if let Some(param) = parameters
+            .iter_mut()
+            .find(|param| param.default.is_none() && param.binding_ident() == Some(ident))
+        {
+            param.default = inherited.default;
+        }
+    }
+
+    parameters
+}
+
+// The index of the parameter that each positional argument is passed to. By default it is the
+// index of the argument. With `positional = fill`, the positional arguments fill the leftmost
+// parameters that are neither given by name nor named-only, and the surplus ones go to the rest parameter.
+fn positional_positions(
+    parameters: &Punctuated<Parameter, Token![,]>,
+    arguments: &Punctuated<Argument, Token![,]>,
+    positional_fill: bool,
+) -> Vec<Option<usize>> {
+    if !positional_fill {
+        return arguments
+            .iter()
+            .enumerate()
+            .map(|(idx, arg)| match arg {
+                Argument::Positioned { .. } | Argument::Placeholder { .. } => Some(idx),
+                Argument::Named { .. } | Argument::Spread { .. } => None,
+            })
+            .collect();
+    }
+
+    let mut free = parameters
+        .iter()
+        .enumerate()
+        .filter(|(_, param)| {
+            !param.rest
+                && param.mode != PassingMode::Named
+                && !arguments.iter().any(|arg| match arg {
+                    Argument::Named { pattern, .. } => param.is_named_by(pattern),
+                    Argument::Positioned { .. }
+                    | Argument::Placeholder { .. }
+                    | Argument::Spread { .. } => false,
+                })
+        })
+        .map(|(idx, _)| idx);
+
+    // without a rest parameter, the surplus ones are not matched by any parameters
+    let mut surplus = match parameters.last() {
+        Some(last) if last.rest => parameters.len() - 1,
+        _ => parameters.len(),
+    };
+
+    arguments
+        .iter()
+        .map(|arg| match arg {
+            Argument::Positioned { .. } | Argument::Placeholder { .. } => {
+                Some(free.next().unwrap_or_else(|| {
+                    surplus += 1;
+                    surplus - 1
+                }))
+            }
+            Argument::Named { .. } | Argument::Spread { .. } => None,
+        })
+        .collect()
+}
+
+struct Matched {
+    // the tokens passed to the parameters
+    fn_args: Vec<TokenStream>,
+    // whether the parameters take their default arguments
+    defaulted: Vec<bool>,
+    // the indexes of the arguments in the order of the parameters they are passed to
+    args_indexes: Vec<usize>,
+}
+
+// matches the arguments to the parameters, `values` are the tokens passed for the arguments,
+// `spread_fields` are the fields of the spread argument passed to the parameters not specified
+fn match_arguments(
+    parameters: &Punctuated<Parameter, Token![,]>,
+    arguments: &Punctuated<Argument, Token![,]>,
+    positions: &[Option<usize>],
+    values: &[Option<TokenStream>],
+    spread_fields: &[Option<TokenStream>],
+) -> syn::Result<Matched> {
+    let mut fn_args = Vec::with_capacity(parameters.len());
+    let mut defaulted = Vec::with_capacity(parameters.len());
+    let mut matched_args_indexes: Vec<usize> = Vec::with_capacity(arguments.len());
+    let mut missing = Vec::new();
+    // every parameter is checked, so that all the errors are reported at once
+    let mut errors = Vec::new();
+
+    for (param_idx, param) in parameters.iter().enumerate() {
+        let arg = if param.rest {
+            get_rest_argument(
+                &mut matched_args_indexes,
+                param_idx,
+                param,
+                arguments,
+                positions,
+                values,
+                spread_fields[param_idx].as_ref(),
+            )
+            .map(Some)
+        } else {
+            get_single_argument(
+                &mut matched_args_indexes,
+                param_idx,
+                param,
+                arguments,
+                positions,
+                values,
+                spread_fields[param_idx].as_ref(),
+            )
+        };
+
+        match arg {
+            Ok(Some((arg, default))) => {
+                fn_args.push(arg);
+                defaulted.push(default);
+            }
+            Ok(None) => missing.push(param),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if !missing.is_empty() {
+        let missing = missing
+            .iter()
+            .map(|param| format!("`{}`", pretty_parameter(param)))
+            .collect::<Vec<_>>();
+
+        let message = match missing.as_slice() {
+            [param] => format!("parameter {} is not specified", param),
+            [params @ .., last] => format!(
+                "parameters {} and {} are not specified",
+                params.join(", "),
+                last
+            ),
+            [] => unreachable!(),
+        };
+
+        // spans the arguments that are written, there is nothing more relevant
+        errors.push(if arguments.is_empty() {
+            syn::Error::new(Span::call_site(), message)
+        } else {
+            syn::Error::new_spanned(arguments, message)
+        });
+    }
+
+    errors.extend(
+        arguments
+            .iter()
+            .enumerate()
+            .filter(|(idx, arg)| {
+                !matched_args_indexes.contains(idx) && !matches!(arg, Argument::Spread { .. })
+            })
+            .map(|(idx, arg)| {
+                let message = match positions[idx] {
+                    // with `positional = fill`, the surplus positional arguments go past the parameters
+                    Some(position) if position >= parameters.len() => format!(
+                        "positional argument `{}` is not matched by any parameters, \
+                        all the parameters that can be passed positionally are already given",
+                        pretty_argument(arg)
+                    ),
+                    _ => match similar_parameter_name(parameters, arg) {
+                        Some(name) => format!(
+                            "argument `{}` is not matched by any parameters, \
+                            a parameter with a similar name exists: `{}`",
+                            pretty_argument(arg),
+                            name
+                        ),
+                        None => format!(
+                            "argument `{}` is not matched by any parameters",
+                            pretty_argument(arg)
+                        ),
+                    },
+                };
+
+                syn::Error::new(arg.span(), message)
+            }),
+    );
+
+    match combine_errors(errors) {
+        Some(e) => Err(e),
+        None => Ok(Matched {
+            fn_args,
+            defaulted,
+            args_indexes: matched_args_indexes,
+        }),
+    }
+}
+
+fn generate_call(
+    target: Target,
+    parameters: &Punctuated<Parameter, Token![,]>,
+    args: &[TokenStream],
+) -> TokenStream {
+    // the arguments of a variadic parameter are empty when nothing is passed to it
+    let args = args.iter().filter(|arg| !arg.is_empty());
+
+    match target {
+        Target::Fn(fn_path) => quote! {
+            #fn_path(#(#args,)*)
+        },
+        Target::UnsafeFn(fn_path) => quote! {
+            unsafe { #fn_path(#(#args,)*) }
+        },
+        Target::Struct(path) => {
+            let fields = parameters.iter().zip(args).map(|(param, arg)| {
+                let field = &param.pat;
+
+                // `field: field` is written in the shorthand form
+                if field.to_token_stream().to_string() == arg.to_string() {
+                    arg.clone()
+                } else {
+                    quote!(#field: #arg)
+                }
+            });
+
+            quote! {
+                #path {
+                    #(#fields,)*
+                }
+            }
+        }
+    }
+}
+
+fn get_single_argument(
     matched_args_indexes: &mut Vec<usize>,
     parameter_index: usize,
-    parameter: &'a Parameter,
-    arguments: &'a Punctuated<Argument, Token![,]>,
-) -> syn::Result<MaybeStartWithDollar<&'a Expr>> {
-    let mut named: Option<(Span, &Expr)> = None;
-    let mut positioned: Option<(Span, &Expr)> = None;
+    parameter: &Parameter,
+    arguments: &Punctuated<Argument, Token![,]>,
+    positions: &[Option<usize>],
+    values: &[Option<TokenStream>],
+    spread_field: Option<&TokenStream>,
+) -> syn::Result<Option<(TokenStream, bool)>> {
+    let mut named: Option<(Span, usize)> = None;
+    let mut positioned: Option<(Span, usize)> = None;
+    let mut errors = Vec::new();
 
     for (arg_idx, arg) in arguments.iter().enumerate() {
         let span = arg.span();
 
         match arg {
-            Argument::Named { pattern, value, .. } => {
-                if *pattern == parameter.pat {
+            Argument::Named { pattern, .. } => {
+                if parameter.is_named_by(pattern) {
+                    errors.extend(check_passing_mode(parameter, arg).err());
+
                     if named.is_some() {
-                        return Err(syn::Error::new(
+                        errors.push(syn::Error::new(
                             span,
                             format!(
                                 "parameter `{}` is specified multiple times by named",
-                                parameter.to_token_stream()
+                                pretty_parameter(parameter)
                             ),
                         ));
+                    } else {
+                        named = Some((span, arg_idx));
                     }
 
-                    named = Some((span, value));
                     matched_args_indexes.push(arg_idx);
                 }
             }
-            Argument::Positioned { value } => {
-                if arg_idx == parameter_index {
-                    positioned = Some((span, value));
+            Argument::Positioned { .. } | Argument::Placeholder { .. } => {
+                if positions[arg_idx] == Some(parameter_index) {
+                    errors.extend(check_passing_mode(parameter, arg).err());
+
+                    positioned = Some((span, arg_idx));
                     matched_args_indexes.push(arg_idx);
                 }
             }
+            Argument::Spread { .. } => {}
         }
     }
 
@@ -136,35 +744,361 @@ fn get_single_argument<'a>(
                     $span,
                     format!(
                         "parameter `{}` is specified both by named and positioned",
-                        parameter.to_token_stream()
+                        pretty_parameter(parameter)
                     ),
                 )
             };
         }
 
-        let mut e = err!(named);
-        e.combine(err!(positioned));
+        errors.push(err!(named));
+        errors.push(err!(positioned));
+    }
+
+    if let Some(e) = combine_errors(errors) {
+        return Err(e);
+    }
+
+    // a placeholder has no value, so the default argument is used like nothing is specified
+    let value = match named.or(positioned) {
+        Some((_, arg_idx)) => values[arg_idx].clone(),
+        None => spread_field.cloned(),
+    };
+
+    let fn_arg = match (value, &parameter.default) {
+        (Some(value), _) => (value, false),
+        (None, Some((_, default))) => (default_argument(default.as_ref()), true),
+        (None, None) => match positioned {
+            Some((span, _)) => {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "parameter `{}` has no default argument, `_` cannot be passed to it",
+                        pretty_parameter(parameter)
+                    ),
+                ));
+            }
+            // reported together with the other missing parameters
+            None => return Ok(None),
+        },
+    };
+
+    Ok(Some(fn_arg))
+}
+
+// collects surplus positional arguments and repeated named arguments in the order they are written
+fn get_rest_argument(
+    matched_args_indexes: &mut Vec<usize>,
+    parameter_index: usize,
+    parameter: &Parameter,
+    arguments: &Punctuated<Argument, Token![,]>,
+    positions: &[Option<usize>],
+    values: &[Option<TokenStream>],
+    spread_field: Option<&TokenStream>,
+) -> syn::Result<(TokenStream, bool)> {
+    let mut collected = Vec::new();
+    let mut errors = Vec::new();
+
+    for (arg_idx, arg) in arguments.iter().enumerate() {
+        match arg {
+            Argument::Named { pattern, .. } if parameter.is_named_by(pattern) => {}
+            Argument::Positioned { .. } if positions[arg_idx] >= Some(parameter_index) => {}
+            Argument::Placeholder { .. } if positions[arg_idx] >= Some(parameter_index) => {
+                errors.push(syn::Error::new(
+                    arg.span(),
+                    format!(
+                        "`_` cannot be passed to the rest parameter `{}`",
+                        pretty_parameter(parameter)
+                    ),
+                ));
+                matched_args_indexes.push(arg_idx);
+                continue;
+            }
+            _ => continue,
+        }
+
+        errors.extend(check_passing_mode(parameter, arg).err());
+
+        collected.extend(&values[arg_idx]);
+        matched_args_indexes.push(arg_idx);
+    }
 
+    if let Some(e) = combine_errors(errors) {
         return Err(e);
     }
 
-    if named.is_none() && positioned.is_none() && parameter.default.is_none() {
-        return Err(syn::Error::new(
-            Span::call_site(),
-            format!(
-                "parameter `{}` is not specified",
-                parameter.to_token_stream()
+    if parameter.variadic {
+        return Ok((quote!(#(#collected),*), false));
+    }
+
+    if collected.is_empty() {
+        if let Some(field) = spread_field {
+            return Ok((field.clone(), false));
+        }
+        if let Some((_, default)) = &parameter.default {
+            return Ok((default_argument(default.as_ref()), true));
+        }
+    }
+
+    let rest = match &parameter.ty {
+        Type::Path(TypePath { path, .. })
+            if path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Vec") =>
+        {
+            quote!(::std::vec![#(#collected),*])
+        }
+        Type::Reference(TypeReference { mutability, .. }) => {
+            quote!(&#mutability [#(#collected),*])
+        }
+        _ => quote!([#(#collected),*]),
+    };
+
+    Ok((rest, false))
+}
+
+// the spread argument must be the last one, and its listed fields must be the names of parameters
+fn check_spread_argument<'a>(
+    parameters: &Punctuated<Parameter, Token![,]>,
+    arguments: &'a Punctuated<Argument, Token![,]>,
+) -> (Option<&'a Argument>, Vec<syn::Error>) {
+    let mut errors = Vec::new();
+    let mut spread = None;
+
+    for (idx, arg) in arguments.iter().enumerate() {
+        let Argument::Spread { fields, .. } = arg else {
+            continue;
+        };
+
+        if idx + 1 != arguments.len() {
+            errors.push(syn::Error::new(
+                arg.span(),
+                "the spread argument `..expr` must be the last argument",
+            ));
+        }
+
+        for field in fields.iter().flat_map(|(_, fields)| fields) {
+            let param = parameters
+                .iter()
+                .find(|param| param.binding_ident() == Some(field));
+
+            match param {
+                Some(param) if param.mode == PassingMode::Positional => {
+                    errors.push(syn::Error::new(
+                        field.span(),
+                        format!("parameter `{}` can only be specified by positioned", field),
+                    ));
+                }
+                Some(_) => {}
+                None => errors.push(syn::Error::new(
+                    field.span(),
+                    format!("field `{}` is not matched by any parameters", field),
+                )),
+            }
+        }
+
+        spread = Some(arg);
+    }
+
+    (spread, errors)
+}
+
+// `1`, `a`, `&mut a.b` or `|a| a + 1`, evaluating it has no side effects
+fn is_inert(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) | Expr::Path(_) | Expr::Closure(_) => true,
+        Expr::Field(_) => is_place_expression(expr),
+        Expr::Reference(ExprReference { expr, .. }) => is_place_expression(expr),
+        Expr::Paren(ExprParen { expr, .. }) | Expr::Group(ExprGroup { expr, .. }) => is_inert(expr),
+        _ => false,
+    }
+}
+
+// `a`, `a.b` or `(a)`, whose fields can be moved out one by one
+fn is_place_expression(expr: &Expr) -> bool {
+    match expr {
+        Expr::Path(ExprPath { qself: None, .. }) => true,
+        Expr::Field(ExprField { base, .. }) => is_place_expression(base),
+        Expr::Paren(ExprParen { expr, .. }) => is_place_expression(expr),
+        _ => false,
+    }
+}
+
+fn combine_errors(errors: Vec<syn::Error>) -> Option<syn::Error> {
+    errors.into_iter().reduce(|mut a, b| {
+        a.combine(b);
+        a
+    })
+}
+
+// the closest name of the parameters to the name of an unmatched named argument, like rustc's
+// "a field with a similar name exists"
+fn similar_parameter_name(
+    parameters: &Punctuated<Parameter, Token![,]>,
+    argument: &Argument,
+) -> Option<String> {
+    let Argument::Named {
+        pattern: Pat::Ident(PatIdent { ident, .. }),
+        ..
+    } = argument
+    else {
+        return None;
+    };
+    let ident = ident.to_string();
+    let max_distance = (ident.chars().count() / 3).max(1);
+
+    parameters
+        .iter()
+        .flat_map(|param| param.binding_ident().into_iter().chain(&param.aliases))
+        .map(|name| (edit_distance(&ident, &name.to_string()), name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name.to_string())
+}
+
+// Levenshtein distance
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+fn generate_signature(target: &Target, parameters: &Punctuated<Parameter, Token![,]>) -> String {
+    let params = parameters
+        .iter()
+        .map(|param| {
+            let default = match &param.default {
+                Some((_, default)) => format!(" = {}", generate_pretty_expr(default.inner())),
+                None => String::new(),
+            };
+
+            format!("{}{}", pretty_parameter(param), default)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match target {
+        Target::Fn(path) | Target::UnsafeFn(path) => {
+            format!("{}({})", target_name(path), params)
+        }
+        Target::Struct(path) => format!("{} {{ {} }}", target_name(path), params),
+    }
+}
+
+// appends the signature of the target to the message of the first error, once is enough
+fn with_signature_note(e: syn::Error, signature: &str) -> syn::Error {
+    combine_errors(
+        (&e).into_iter()
+            .enumerate()
+            .map(|(idx, e)| match idx {
+                0 => syn::Error::new(
+                    e.span(),
+                    format!("{}\n\nnote: the signature is `{}`", e, signature),
+                ),
+                _ => e,
+            })
+            .collect(),
+    )
+    .unwrap_or(e)
+}
+
+// `a: u32`, the parameter in the messages, its default argument may be a call to an accessor
+fn pretty_parameter(param: &Parameter) -> String {
+    format!(
+        "{}: {}",
+        pretty_pattern(param),
+        generate_pretty_ty(&param.ty)
+    )
+}
+
+fn pretty_pattern(param: &Parameter) -> String {
+    match &param.name {
+        Some(name) => name.to_string(),
+        None => generate_pretty_pat(&param.pat),
+    }
+}
+
+fn pretty_argument(arg: &Argument) -> String {
+    match arg {
+        Argument::Positioned { value } => generate_pretty_expr(value),
+        Argument::Named { pattern, value, .. } => format!(
+            "{} = {}",
+            generate_pretty_pat(pattern),
+            generate_pretty_expr(value)
+        ),
+        Argument::Placeholder { .. } => "_".to_string(),
+        Argument::Spread { expr, fields, .. } => match fields {
+            Some((_, fields)) => format!(
+                "..{} {{ {} }}",
+                generate_pretty_expr(expr),
+                fields
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
             ),
-        ));
+            None => format!("..{}", generate_pretty_expr(expr)),
+        },
     }
+}
 
-    let fn_arg = named
-        .map(|(_, n)| MaybeStartWithDollar::Normal(n))
-        .unwrap_or_else(|| {
-            positioned
-                .map(|(_, p)| MaybeStartWithDollar::Normal(p))
-                .unwrap_or_else(|| parameter.default.as_ref().unwrap().1.as_ref())
-        });
+fn target_name(path: &MaybeStartWithDollar<Path>) -> String {
+    path.inner()
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default()
+}
+
+fn check_passing_mode(parameter: &Parameter, argument: &Argument) -> syn::Result<()> {
+    let msg = match (parameter.mode, argument) {
+        (PassingMode::Named, Argument::Positioned { .. } | Argument::Placeholder { .. }) => {
+            "can only be specified by named"
+        }
+        (PassingMode::Positional, Argument::Named { .. }) => "can only be specified by positioned",
+        _ => return Ok(()),
+    };
 
-    Ok(fn_arg)
+    Err(syn::Error::new(
+        argument.span(),
+        format!("parameter `{}` {}", pretty_pattern(parameter), msg),
+    ))
+}
+
+// Local variables in the default argument resolve at the definition site of `nade_helper`, so a
+// local variable at the call site with the same name can't be captured, while paths still resolve
+// at the call site. `$crate` keeps its span, which records the crate it refers to.
+fn default_argument(default: MaybeStartWithDollar<&Expr>) -> TokenStream {
+    respan_mixed_site(default.to_token_stream())
+}
+
+fn respan_mixed_site(tokens: TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tree| match tree {
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), respan_mixed_site(group.stream()));
+                new_group.set_span(Span::mixed_site().located_at(group.span()));
+                TokenTree::Group(new_group)
+            }
+            TokenTree::Ident(ident) if ident == "$crate" => TokenTree::Ident(ident),
+            mut tree => {
+                tree.set_span(Span::mixed_site().located_at(tree.span()));
+                tree
+            }
+        })
+        .collect()
 }
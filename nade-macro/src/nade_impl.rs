@@ -0,0 +1,203 @@
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_quote, spanned::Spanned, FnArg, GenericParam, ImplItem, ImplItemFn, ItemImpl, Meta, Path,
+    PathArguments, Type, TypePath,
+};
+
+use crate::{
+    default_accessor::{self, Holder},
+    maybe_start_with_dollar::StartWithDollar,
+    nade::{
+        drain_filter, extract_parameters_and_docs, generate_macro, mark_generic_parameters,
+        parse_macro_name, MacroDef,
+    },
+    path_attribute::PathAttr,
+};
+
+pub(crate) fn generate(
+    module_path: Option<StartWithDollar<Path>>,
+    item_impl: &mut ItemImpl,
+) -> syn::Result<TokenStream> {
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        return Err(syn::Error::new(
+            path.span(),
+            "`#[nade]` cannot be used on trait implementations",
+        ));
+    }
+
+    let path_attr = PathAttr::parse_attrs(&mut item_impl.attrs)?;
+
+    let self_path = extract_self_path(&item_impl.self_ty)?;
+    let self_ident = self_path.segments.last().unwrap().ident.clone();
+    let module_path = module_path.map(|path| quote!(#path::));
+
+    let mut macro_defs = Vec::new();
+    let mut default_accessors = Vec::new();
+    let mut default_checks = Vec::new();
+
+    for item in item_impl.items.iter_mut() {
+        let ImplItem::Fn(fun) = item else {
+            continue;
+        };
+
+        let nade_attrs = drain_filter(&mut fun.attrs, |attr| attr.path().is_ident("nade"));
+
+        if nade_attrs.is_empty() && !has_nade_parameters(fun) {
+            continue;
+        }
+
+        let macro_name = parse_macro_name(&nade_attrs, "method")?;
+
+        replace_self_in_defaults(fun, &self_path);
+
+        let (mut parameters, parameter_docs) = extract_parameters_and_docs(&mut fun.sig.inputs)?;
+        mark_generic_parameters(&mut parameters, &item_impl.generics);
+        mark_generic_parameters(&mut parameters, &fun.sig.generics);
+
+        let name = &fun.sig.ident;
+
+        // the accessors are reached through the same path as the method, the generic parameters
+        // of the `impl` block can't always be inferred from the call, so they only get checks
+        if item_impl.generics.params.is_empty() {
+            let (accessors, checks) = default_accessor::generate(
+                name,
+                &fun.vis,
+                &fun.sig.generics,
+                Holder::Method {
+                    module_path: module_path.as_ref(),
+                    self_path: &self_path,
+                    name: format_ident!("__nade_defaults_{}", name),
+                },
+                &mut parameters,
+            )?;
+
+            default_accessors.extend(accessors);
+
+            for check in checks {
+                default_checks.push(syn::parse2::<ImplItem>(check)?);
+            }
+        } else {
+            // `Self`, and the type that replaced it in the defaults, depend on the generic
+            // parameters of the `impl` block as well
+            let mut generics = item_impl.generics.clone();
+            generics
+                .params
+                .extend(fun.sig.generics.params.iter().cloned());
+            for ident in [format_ident!("Self"), self_ident.clone()] {
+                generics.params.push(GenericParam::Type(ident.into()));
+            }
+
+            for check in default_accessor::generate_checks(name, &generics, &parameters) {
+                default_checks.push(syn::parse2::<ImplItem>(check)?);
+            }
+        }
+
+        let link_target = format!("{}::{}", self_path.to_token_stream(), name).replace(' ', "");
+
+        let macro_def = generate_macro(
+            &path_attr,
+            MacroDef {
+                attrs: &fun.attrs,
+                vis: &fun.vis,
+                name: macro_name.unwrap_or_else(|| name.clone()),
+                link_doc: format!(
+                    "Wrapper macro for method [`{}`]({}()).",
+                    link_target, link_target
+                ),
+                parameters,
+                parameter_docs,
+                output: fun.sig.output.clone(),
+                target: quote!(#module_path #self_path::#name),
+                forward: None,
+            },
+        );
+
+        fun.attrs
+            .push(parse_quote!(#[allow(clippy::too_many_arguments)]));
+
+        macro_defs.push(macro_def);
+    }
+
+    item_impl.items.extend(default_checks);
+
+    let expand = quote! {
+        #item_impl
+
+        #(#default_accessors)*
+
+        #(#macro_defs)*
+    };
+
+    Ok(expand)
+}
+
+// generic arguments are removed, they are inferred at the call site
+fn extract_self_path(self_ty: &Type) -> syn::Result<Path> {
+    let Type::Path(TypePath { qself: None, path }) = self_ty else {
+        return Err(syn::Error::new(
+            self_ty.span(),
+            "`#[nade]` only supports `impl` blocks whose self type is a path",
+        ));
+    };
+
+    let mut path = path.clone();
+
+    for segment in path.segments.iter_mut() {
+        segment.arguments = PathArguments::None;
+    }
+
+    Ok(path)
+}
+
+fn has_nade_parameters(fun: &ImplItemFn) -> bool {
+    fun.sig.inputs.iter().any(|arg| {
+        let attrs = match arg {
+            FnArg::Receiver(r) => &r.attrs,
+            FnArg::Typed(t) => &t.attrs,
+        };
+
+        attrs.iter().any(|attr| attr.path().is_ident("nade"))
+    })
+}
+
+// `Self` is not available where the macro is called
+fn replace_self_in_defaults(fun: &mut ImplItemFn, self_path: &Path) {
+    for arg in fun.sig.inputs.iter_mut() {
+        let FnArg::Typed(pat_type) = arg else {
+            continue;
+        };
+
+        for attr in pat_type.attrs.iter_mut() {
+            if !attr.path().is_ident("nade") {
+                continue;
+            }
+
+            if let Meta::List(list) = &mut attr.meta {
+                list.tokens = replace_self(list.tokens.clone(), self_path);
+            }
+        }
+    }
+}
+
+pub(crate) fn replace_self(tokens: TokenStream, self_path: &Path) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|tree| match tree {
+            TokenTree::Ident(ident) if ident == "Self" => {
+                let mut path = self_path.clone();
+                path.segments
+                    .iter_mut()
+                    .for_each(|segment| segment.ident.set_span(ident.span()));
+                path.into_token_stream()
+            }
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), replace_self(group.stream(), self_path));
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group).into_token_stream()
+            }
+            tree => tree.into_token_stream(),
+        })
+        .collect()
+}
@@ -0,0 +1,94 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_quote, spanned::Spanned, Fields, ItemStruct, Path};
+
+use crate::{
+    maybe_start_with_dollar::StartWithDollar,
+    nade::{
+        check_parameters, collect_docs, extract_parameter_and_doc, generate_macro,
+        mark_generic_parameters, MacroDef,
+    },
+    parameter::Parameter,
+    parameter_doc::ParameterDoc,
+    path_attribute::PathAttr,
+};
+
+pub(crate) fn generate(
+    module_path: Option<StartWithDollar<Path>>,
+    item_struct: &mut ItemStruct,
+) -> syn::Result<TokenStream> {
+    let path_attr = PathAttr::parse_attrs(&mut item_struct.attrs)?;
+
+    let struct_token = match &item_struct.fields {
+        Fields::Named(_) => quote!(struct),
+        Fields::Unnamed(_) => quote!(),
+        Fields::Unit => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`#[nade]` does not support unit structs",
+            ));
+        }
+    };
+
+    let (mut parameters, parameter_docs) =
+        extract_fields_parameters_and_docs(&mut item_struct.fields)?;
+    mark_generic_parameters(&mut parameters, &item_struct.generics);
+
+    let name = &item_struct.ident;
+    let (_, ty_generics, _) = item_struct.generics.split_for_impl();
+    let module_path = module_path.map(|path| quote!(#path::));
+
+    let macro_def = generate_macro(
+        &path_attr,
+        MacroDef {
+            attrs: &item_struct.attrs,
+            vis: &item_struct.vis,
+            name: name.clone(),
+            link_doc: format!("Construction macro for struct [`{}`].", name),
+            parameters,
+            parameter_docs,
+            output: parse_quote!(-> #name #ty_generics),
+            target: quote!(#struct_token #module_path #name),
+            forward: None,
+        },
+    );
+
+    let expand = quote! {
+        #item_struct
+
+        #macro_def
+    };
+
+    Ok(expand)
+}
+
+// tuple fields are named `_0`, `_1`, ...
+pub(crate) fn extract_fields_parameters_and_docs(
+    fields: &mut Fields,
+) -> syn::Result<(Vec<Parameter>, Vec<ParameterDoc>)> {
+    let mut parameters = Vec::new();
+    let mut parameter_docs = Vec::new();
+
+    for (idx, field) in fields.iter_mut().enumerate() {
+        let ident = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("_{}", idx, span = field.ty.span()),
+        };
+        let docs = collect_docs(&field.attrs);
+
+        let (parameter, parameter_doc) = extract_parameter_and_doc(
+            &mut field.attrs,
+            parse_quote!(#ident),
+            field.colon_token.unwrap_or_default(),
+            field.ty.clone(),
+            docs,
+        )?;
+
+        parameters.push(parameter);
+        parameter_docs.push(parameter_doc);
+    }
+
+    check_parameters(&parameters)?;
+
+    Ok((parameters, parameter_docs))
+}
@@ -0,0 +1,119 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, FnArg, Path, ReturnType, Token, Visibility,
+};
+
+use crate::{
+    nade::{drain_filter, extract_parameters_and_docs, generate_macro, parse_macro_name, MacroDef},
+    path_attribute::PathAttr,
+};
+
+/// The declarations in `nade::wrap! { .. }`.
+pub(crate) struct Wrap {
+    functions: Vec<WrappedFn>,
+}
+
+// `pub fn path::to::function(parameters) -> Output;`, the function is not defined by the declaration
+struct WrappedFn {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    dollar_token: Option<Token![$]>,
+    path: Path,
+    inputs: Punctuated<FnArg, Token![,]>,
+    output: ReturnType,
+}
+
+impl Parse for Wrap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut functions = Vec::new();
+
+        while !input.is_empty() {
+            functions.push(input.parse()?);
+        }
+
+        Ok(Wrap { functions })
+    }
+}
+
+impl Parse for WrappedFn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse::<Visibility>()?;
+        input.parse::<Token![fn]>()?;
+        let dollar_token = input.parse::<Option<Token![$]>>()?;
+        // the mod style, so that the parameters are not parsed as `Fn(A) -> B` arguments
+        let path = input.call(Path::parse_mod_style)?;
+
+        let content;
+        parenthesized!(content in input);
+        let inputs = content.parse_terminated(FnArg::parse, Token![,])?;
+
+        let output = input.parse::<ReturnType>()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(WrappedFn {
+            attrs,
+            vis,
+            dollar_token,
+            path,
+            inputs,
+            output,
+        })
+    }
+}
+
+pub(crate) fn generate(wrap: Wrap) -> syn::Result<TokenStream> {
+    let mut macro_defs = Vec::new();
+
+    for function in wrap.functions {
+        macro_defs.push(generate_wrapped_fn(function)?);
+    }
+
+    Ok(quote! {
+        #(#macro_defs)*
+    })
+}
+
+fn generate_wrapped_fn(function: WrappedFn) -> syn::Result<TokenStream> {
+    let WrappedFn {
+        mut attrs,
+        vis,
+        dollar_token,
+        path,
+        mut inputs,
+        output,
+    } = function;
+
+    let path_attr = PathAttr::parse_attrs(&mut attrs)?;
+
+    let nade_attrs = drain_filter(&mut attrs, |attr| attr.path().is_ident("nade"));
+    let macro_name = parse_macro_name(&nade_attrs, "function")?;
+
+    let (parameters, parameter_docs) = extract_parameters_and_docs(&mut inputs)?;
+
+    let name = &path.segments.last().unwrap().ident;
+    // `$crate::a::b` is linked as `crate::a::b`
+    let link_target = path.to_token_stream().to_string().replace(' ', "");
+
+    Ok(generate_macro(
+        &path_attr,
+        MacroDef {
+            attrs: &attrs,
+            vis: &vis,
+            name: macro_name.unwrap_or_else(|| name.clone()),
+            link_doc: format!(
+                "Wrapper macro for function [`{}`]({}()).",
+                link_target, link_target
+            ),
+            parameters,
+            parameter_docs,
+            output,
+            target: quote!(#dollar_token #path),
+            forward: None,
+        },
+    ))
+}
@@ -1,52 +1,362 @@
-use proc_macro2::TokenStream;
-use quote::ToTokens;
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    Expr, Pat, Token, Type,
+    punctuated::Punctuated,
+    Attribute, Expr, Ident, Lit, Pat, PatIdent, Token, Type,
 };
 
-use crate::maybe_start_with_dollar::MaybeStartWithDollar;
+use crate::{
+    maybe_start_with_dollar::MaybeStartWithDollar,
+    parameter_attribute::{ParameterAttr, PassingMode},
+};
 
 pub(crate) struct Parameter {
+    pub(crate) rest: bool,
+    // the `...` of a variadic foreign function, a rest parameter whose arguments are passed as they are
+    pub(crate) variadic: bool,
+    // the type mentions generic parameters or `Self`, so it can't be written where the macro is called
+    pub(crate) generic: bool,
+    pub(crate) mode: PassingMode,
+    pub(crate) aliases: Vec<Ident>,
+    pub(crate) name: Option<Ident>,
     pub(crate) pat: Pat,
     colon_token: Token![:],
-    ty: Type,
+    pub(crate) ty: Type,
     pub(crate) default: Option<(Token![=], MaybeStartWithDollar<Expr>)>,
 }
 
 impl Parameter {
-    pub(crate) fn new(
-        pat: Pat,
-        colon_token: Token![:],
-        ty: Type,
-        default: Option<MaybeStartWithDollar<Expr>>,
-    ) -> Self {
+    pub(crate) fn new(pat: Pat, colon_token: Token![:], ty: Type, attr: ParameterAttr) -> Self {
+        let ParameterAttr {
+            default,
+            rest,
+            mode,
+            aliases,
+            name,
+        } = attr;
+
         Self {
+            rest,
+            variadic: false,
+            generic: false,
+            mode,
+            aliases,
+            name,
             pat,
             colon_token,
             ty,
             default: default.map(|expr| (<Token![=]>::default(), expr)),
         }
     }
+
+    /// The parameter as written in the function signature, without the markers.
+    pub(crate) fn signature(&self) -> TokenStream {
+        let Parameter {
+            pat,
+            colon_token,
+            ty,
+            default,
+            ..
+        } = self;
+
+        match default {
+            Some((eq_token, expr)) => quote!(#pat #colon_token #ty #eq_token #expr),
+            None => quote!(#pat #colon_token #ty),
+        }
+    }
+
+    /// Whether a named argument with this pattern is passed to the parameter,
+    /// `ref` and `mut` binding modes are ignored.
+    pub(crate) fn is_named_by(&self, pattern: &Pat) -> bool {
+        let pattern = pattern_without_binding_modes(pattern.to_token_stream());
+
+        pattern == pattern_without_binding_modes(self.pat.to_token_stream())
+            || self
+                .name
+                .iter()
+                .chain(&self.aliases)
+                .any(|ident| *ident == pattern)
+    }
+
+    /// The identifier that default arguments of later parameters use to refer to this parameter.
+    pub(crate) fn binding_ident(&self) -> Option<&Ident> {
+        match (&self.name, &self.pat) {
+            (Some(name), _) => Some(name),
+            (
+                None,
+                Pat::Ident(PatIdent {
+                    ident,
+                    subpat: None,
+                    ..
+                }),
+            ) if ident != "self" => Some(ident),
+            _ => None,
+        }
+    }
+
+    /// Whether the default argument mentions the identifier as a variable.
+    pub(crate) fn default_refers_to(&self, ident: &Ident) -> bool {
+        match &self.default {
+            Some((_, default)) => refers_to(default.inner().to_token_stream(), ident),
+            None => false,
+        }
+    }
+}
+
+// skips field names, method names, path segments and macro names, like `a.len()`, `a::B` and `a!()`,
+// and the identifiers bound by closure parameters and `let`s, but sees the inline captures of
+// format strings, like `format!("{a}")`
+fn refers_to(tokens: TokenStream, ident: &Ident) -> bool {
+    let trees = tokens.into_iter().collect::<Vec<_>>();
+    let mut idx = 0;
+
+    while idx < trees.len() {
+        match &trees[idx] {
+            TokenTree::Punct(p) if p.as_char() == '|' && starts_closure(&trees, idx) => {
+                let params_end = (idx + 1..trees.len())
+                    .find(|idx| is_punct(&trees[*idx], '|'))
+                    .unwrap_or(trees.len());
+
+                // the body ends at the end of the enclosing expression
+                if binds(&trees[idx + 1..params_end], ident) {
+                    idx = (params_end..trees.len())
+                        .find(|idx| is_punct(&trees[*idx], ',') || is_punct(&trees[*idx], ';'))
+                        .unwrap_or(trees.len());
+                    continue;
+                }
+
+                idx = params_end + 1;
+            }
+            TokenTree::Ident(i) if i == "let" => {
+                let eq = (idx + 1..trees.len())
+                    .find(|idx| is_punct(&trees[*idx], '=') || is_punct(&trees[*idx], ';'))
+                    .unwrap_or(trees.len());
+
+                if binds(&trees[idx + 1..eq], ident) {
+                    // `if let` and `while let` bind in the following block, `let` in the rest of the block
+                    let conditional = idx > 0
+                        && matches!(&trees[idx - 1], TokenTree::Ident(i) if i == "if" || i == "while");
+                    let init_end = (eq..trees.len())
+                        .find(|idx| match &trees[*idx] {
+                            TokenTree::Group(group) if conditional => {
+                                group.delimiter() == Delimiter::Brace
+                            }
+                            tree => !conditional && is_punct(tree, ';'),
+                        })
+                        .unwrap_or(trees.len());
+
+                    if refers_to(trees[eq..init_end].iter().cloned().collect(), ident) {
+                        return true;
+                    }
+                    if !conditional {
+                        return false;
+                    }
+
+                    idx = init_end + 1;
+                    continue;
+                }
+
+                idx = eq;
+            }
+            TokenTree::Ident(i) => {
+                let after_dot_or_colon = idx > 0
+                    && matches!(&trees[idx - 1], TokenTree::Punct(p) if p.as_char() == '.' || p.as_char() == ':');
+                let before_colon_or_bang = matches!(
+                    trees.get(idx + 1),
+                    Some(TokenTree::Punct(p)) if p.as_char() == ':' || p.as_char() == '!'
+                );
+
+                if i == ident && !after_dot_or_colon && !before_colon_or_bang {
+                    return true;
+                }
+
+                idx += 1;
+            }
+            TokenTree::Group(group) => {
+                let macro_call = idx > 1
+                    && is_punct(&trees[idx - 1], '!')
+                    && matches!(&trees[idx - 2], TokenTree::Ident(_));
+
+                if refers_to(group.stream(), ident)
+                    || macro_call && format_string_captures(group.stream(), ident)
+                {
+                    return true;
+                }
+
+                idx += 1;
+            }
+            _ => idx += 1,
+        }
+    }
+
+    false
+}
+
+// `|` is a closure when it can't be a binary operator, i.e. not after an operand
+fn starts_closure(trees: &[TokenTree], idx: usize) -> bool {
+    match idx.checked_sub(1).map(|idx| &trees[idx]) {
+        None | Some(TokenTree::Punct(_)) => true,
+        Some(TokenTree::Ident(i)) => i == "move" || i == "return",
+        Some(_) => false,
+    }
+}
+
+// the identifiers bound by a pattern, without the paths and the types, like `S(a)` and `a: u32`
+fn binds(pattern: &[TokenTree], ident: &Ident) -> bool {
+    let mut in_type = false;
+
+    pattern.iter().enumerate().any(|(idx, tree)| match tree {
+        TokenTree::Punct(p) if p.as_char() == ',' => {
+            in_type = false;
+            false
+        }
+        TokenTree::Punct(p) if p.as_char() == ':' => {
+            let path_separator = p.spacing() == Spacing::Joint
+                || idx > 0 && matches!(&pattern[idx - 1], TokenTree::Punct(p) if p.as_char() == ':' && p.spacing() == Spacing::Joint);
+            in_type |= !path_separator;
+            false
+        }
+        TokenTree::Ident(i) if !in_type => {
+            let is_path = match pattern.get(idx + 1) {
+                Some(TokenTree::Group(_)) => true,
+                Some(TokenTree::Punct(p)) => p.as_char() == ':' && p.spacing() == Spacing::Joint,
+                _ => false,
+            };
+
+            i == ident && !is_path
+        }
+        TokenTree::Group(group) if !in_type => {
+            binds(&group.stream().into_iter().collect::<Vec<_>>(), ident)
+        }
+        _ => false,
+    })
+}
+
+// `{a}` or `{a:?}` in the first string literal of a macro call, `{{` is an escaped brace
+fn format_string_captures(tokens: TokenStream, ident: &Ident) -> bool {
+    let Some(format_string) = tokens.into_iter().find_map(|tree| match tree {
+        TokenTree::Literal(literal) => match Lit::new(literal) {
+            Lit::Str(s) => Some(s.value()),
+            _ => None,
+        },
+        _ => None,
+    }) else {
+        return false;
+    };
+
+    let ident = ident.to_string();
+    let mut rest = format_string.as_str();
+
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+
+        let end = rest.find(['}', ':']).unwrap_or(rest.len());
+
+        if rest[..end].trim() == ident {
+            return true;
+        }
+
+        rest = &rest[end..];
+    }
+
+    false
+}
+
+fn is_punct(tree: &TokenTree, ch: char) -> bool {
+    matches!(tree, TokenTree::Punct(p) if p.as_char() == ch)
+}
+
+fn pattern_without_binding_modes(tokens: TokenStream) -> String {
+    tokens
+        .into_iter()
+        .filter_map(|tree| match tree {
+            TokenTree::Ident(ident) if ident == "ref" || ident == "mut" => None,
+            TokenTree::Group(group) => Some(format!(
+                "{:?}{}",
+                group.delimiter(),
+                pattern_without_binding_modes(group.stream())
+            )),
+            tree => Some(tree.to_string()),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 impl ToTokens for Parameter {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.pat.to_tokens(tokens);
-        self.colon_token.to_tokens(tokens);
-        self.ty.to_tokens(tokens);
-        if let Some((eq_token, expr)) = &self.default {
-            eq_token.to_tokens(tokens);
-            expr.to_tokens(tokens);
+        if self.rest {
+            tokens.extend(quote!(#[rest]));
+        }
+        if self.variadic {
+            tokens.extend(quote!(#[variadic]));
         }
+        if self.generic {
+            tokens.extend(quote!(#[generic]));
+        }
+        match self.mode {
+            PassingMode::Any => {}
+            PassingMode::Named => tokens.extend(quote!(#[named])),
+            PassingMode::Positional => tokens.extend(quote!(#[positional])),
+        }
+        if let Some(name) = &self.name {
+            tokens.extend(quote!(#[name(#name)]));
+        }
+        if !self.aliases.is_empty() {
+            let aliases = &self.aliases;
+            tokens.extend(quote!(#[alias(#(#aliases),*)]));
+        }
+        tokens.extend(self.signature());
     }
 }
 
 impl Parse for Parameter {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut rest = false;
+        let mut variadic = false;
+        let mut generic = false;
+        let mut mode = PassingMode::Any;
+        let mut aliases = Vec::new();
+        let mut name = None;
+
+        for attr in input.call(Attribute::parse_outer)? {
+            if attr.path().is_ident("rest") {
+                rest = true;
+            } else if attr.path().is_ident("variadic") {
+                variadic = true;
+            } else if attr.path().is_ident("generic") {
+                generic = true;
+            } else if attr.path().is_ident("named") {
+                mode = PassingMode::Named;
+            } else if attr.path().is_ident("positional") {
+                mode = PassingMode::Positional;
+            } else if attr.path().is_ident("name") {
+                name = Some(attr.parse_args::<Ident>()?);
+            } else if attr.path().is_ident("alias") {
+                aliases.extend(
+                    attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?,
+                );
+            } else {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected `#[rest]`, `#[variadic]`, `#[generic]`, `#[named]`, `#[positional]`, `#[name(..)]` or `#[alias(..)]`",
+                ));
+            }
+        }
+
         let pat = input.call(Pat::parse_single)?;
         let colon_token = input.parse::<Token![:]>()?;
-        let ty = input.parse::<Type>()?;
+        let ty = if variadic {
+            let dot3_token = input.parse::<Token![...]>()?;
+            Type::Verbatim(dot3_token.into_token_stream())
+        } else {
+            input.parse::<Type>()?
+        };
 
         let default = if input.peek(Token![=]) {
             Some((input.parse()?, input.parse()?))
@@ -55,6 +365,12 @@ impl Parse for Parameter {
         };
 
         Ok(Parameter {
+            rest,
+            variadic,
+            generic,
+            mode,
+            aliases,
+            name,
             pat,
             colon_token,
             ty,
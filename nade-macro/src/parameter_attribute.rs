@@ -0,0 +1,149 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    spanned::Spanned,
+    Attribute, Expr, Ident, Meta, MetaList, Token,
+};
+
+use crate::maybe_start_with_dollar::MaybeStartWithDollar;
+
+#[derive(Default)]
+pub(crate) struct ParameterAttr {
+    pub(crate) default: Option<MaybeStartWithDollar<Expr>>,
+    pub(crate) rest: bool,
+    pub(crate) mode: PassingMode,
+    pub(crate) aliases: Vec<Ident>,
+    pub(crate) name: Option<Ident>,
+}
+
+/// How the arguments of a parameter can be passed.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum PassingMode {
+    #[default]
+    Any,
+    Named,
+    Positional,
+}
+
+impl ParameterAttr {
+    pub(crate) fn parse_attr(attr: Attribute) -> syn::Result<Self> {
+        match attr.meta {
+            Meta::Path(_) => Ok(ParameterAttr {
+                default: Some(MaybeStartWithDollar::Normal(parse_quote!(
+                    ::core::default::Default::default()
+                ))),
+                ..Default::default()
+            }),
+            Meta::List(MetaList { tokens, .. }) => syn::parse2(tokens),
+            Meta::NameValue(a) => Err(syn::Error::new(
+                a.span(),
+                "the `#[nade]` attribute does not support `#[nade = ..]`",
+            )),
+        }
+    }
+}
+
+// `#[nade(expr, option, ..)]`, the options are identifiers which can't be confused with the default argument
+impl Parse for ParameterAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attr = ParameterAttr::default();
+
+        while !input.is_empty() {
+            if peek_option(input, "rest") {
+                let ident = input.parse::<Ident>()?;
+                if attr.rest {
+                    return Err(syn::Error::new(ident.span(), "duplicate `rest` argument"));
+                }
+                attr.rest = true;
+            } else if peek_option(input, "named") || peek_option(input, "positional") {
+                let ident = input.parse::<Ident>()?;
+                let mode = if ident == "named" {
+                    PassingMode::Named
+                } else {
+                    PassingMode::Positional
+                };
+
+                if attr.mode == mode {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("duplicate `{}` argument", ident),
+                    ));
+                }
+                if attr.mode != PassingMode::Any {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "`named` and `positional` cannot be used together",
+                    ));
+                }
+                attr.mode = mode;
+            } else if peek_name_value(input, "name") {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                let name = input.parse::<Ident>()?;
+                if attr.name.is_some() {
+                    return Err(syn::Error::new(name.span(), "duplicate `name` argument"));
+                }
+                attr.name = Some(name);
+            } else if peek_name_value(input, "alias") {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                let alias = input.parse::<Ident>()?;
+                if attr.aliases.contains(&alias) {
+                    return Err(syn::Error::new(
+                        alias.span(),
+                        format!("duplicate alias `{}`", alias),
+                    ));
+                }
+                attr.aliases.push(alias);
+            } else {
+                let default = input.parse::<MaybeStartWithDollar<Expr>>()?;
+
+                if let Expr::Assign(_) = default.inner() {
+                    return Err(syn::Error::new(
+                        default.span(),
+                        "assignment expression is not supported \
+                            because it is not possible to distinguish \
+                            whether it is a named general expression \
+                            or a non-named assignment expression.",
+                    ));
+                }
+
+                if attr.default.is_some() {
+                    return Err(syn::Error::new(
+                        default.span(),
+                        "duplicate default argument",
+                    ));
+                }
+
+                attr.default = Some(default);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(attr)
+    }
+}
+
+fn peek_option(input: ParseStream, option: &str) -> bool {
+    let fork = input.fork();
+
+    match fork.parse::<Ident>() {
+        Ok(ident) => ident == option && (fork.is_empty() || fork.peek(Token![,])),
+        Err(_) => false,
+    }
+}
+
+// `option = ident`, an assignment expression is never a valid default argument
+fn peek_name_value(input: ParseStream, option: &str) -> bool {
+    let fork = input.fork();
+
+    match fork.parse::<Ident>() {
+        Ok(ident) => ident == option && fork.peek(Token![=]) && !fork.peek(Token![==]),
+        Err(_) => false,
+    }
+}
@@ -1,8 +1,14 @@
-use syn::{Expr, LitStr, Pat, Type};
+use syn::{Expr, Ident, LitStr, Pat, Type};
+
+use crate::parameter_attribute::PassingMode;
 
 pub(crate) struct ParameterDoc {
     pub(crate) pattern: Pat,
     pub(crate) ty: Type,
     pub(crate) docs: Vec<LitStr>,
     pub(crate) default: Option<Expr>,
+    pub(crate) rest: bool,
+    pub(crate) mode: PassingMode,
+    pub(crate) aliases: Vec<Ident>,
+    pub(crate) name: Option<Ident>,
 }
@@ -1,6 +1,6 @@
 #![doc = include_str!("../README.md")]
 
-pub use nade_macro::nade;
+pub use nade_macro::{nade, wrap};
 
 #[doc(hidden)]
 pub mod base {
@@ -70,9 +70,36 @@ fn generic() {
     assert_eq!(foo!("world"), "world");
     assert_eq!(foo!(a = "a"), "a");
     assert_eq!(foo!(a = String::from("abcd")), "abcd");
+
+    #[nade]
+    fn bar<T: Default + ToString, const N: usize>(
+        #[nade(T::default())] a: T,
+        #[nade([0; N])] b: [u8; N],
+        #[nade(Vec::<T>::new())] c: Vec<T>,
+    ) -> String {
+        format!("{} {} {}", a.to_string(), b.len(), c.len())
+    }
+
+    assert_eq!(bar!(b = [1, 2], c = vec![0u8]), "0 2 1");
+    assert_eq!(bar!(1u8, [0; 3]), "1 3 0");
+    assert_eq!(bar!(c = vec![String::from("a")], b = []), " 0 1");
+    assert_eq!(bar::a::<u32>(), 0);
+
+    #[nade]
+    fn baz<T>(value: T, #[nade(Option::<T>::None)] fallback: Option<T>) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        fallback.filter(|fallback| *fallback != value)
+    }
+
+    assert_eq!(baz!(1), None);
+    assert_eq!(baz!(1, Some(2)), Some(2));
 }
 
 #[test]
+// `pub` makes the generated macro `#[macro_export]`, which is a non-local definition in a function
+#[allow(non_local_definitions)]
 fn pattern_matching() {
     pub struct One<T>(T);
 
@@ -84,4 +111,619 @@ fn pattern_matching() {
     assert_eq!(foo!(), 1);
     assert_eq!(foo!((One(a), _) = (One(2), Some(3))), 2);
     assert_eq!(foo!((One(3), None)), 3);
+
+    #[nade]
+    fn bar(#[nade(name = pair, (One(1), Some(2)))] (One(a), _): (One<u32>, Option<u32>)) -> u32 {
+        a
+    }
+
+    assert_eq!(bar!(), 1);
+    assert_eq!(bar!(pair = (One(2), Some(3))), 2);
+    assert_eq!(bar!((One(a), _) = (One(3), None)), 3);
+}
+
+#[test]
+fn binding_mode() {
+    #[nade]
+    #[allow(clippy::toplevel_ref_arg)]
+    fn foo(mut a: u32, #[nade(1)] ref b: u32) -> u32 {
+        a += b;
+        a
+    }
+
+    assert_eq!(foo!(a = 1), 2);
+    assert_eq!(foo!(a = 1, b = 2), 3);
+    assert_eq!(foo!(mut a = 1, ref b = 2), 3);
+}
+
+#[test]
+fn ref_receiver() {
+    #[derive(Default)]
+    struct Counter(u32);
+
+    #[nade]
+    impl Counter {
+        #[nade]
+        fn get(&self, #[nade(1)] step: u32) -> u32 {
+            self.0 + step
+        }
+
+        #[nade]
+        fn merge(&self, #[nade(Self::default())] other: Self) -> Self {
+            Self(self.0 + other.0)
+        }
+    }
+
+    let counter = Counter(1);
+
+    assert_eq!(get!(&counter), 2);
+    assert_eq!(get!(&counter, 2), 3);
+    assert_eq!(get!(&counter, step = 3), 4);
+    assert_eq!(get!(self = &counter, step = 4), 5);
+    assert_eq!(get!(step = 5, self = &counter), 6);
+    assert_eq!(merge!(&counter).0, 1);
+    assert_eq!(merge!(&counter, Counter(2)).0, 3);
+    assert_eq!(Counter::__nade_defaults_merge().other().0, 0);
+}
+
+#[test]
+fn mut_ref_receiver() {
+    struct Counter(u32);
+
+    #[nade]
+    impl Counter {
+        #[nade]
+        fn add(&mut self, #[nade(1)] step: u32) -> u32 {
+            self.0 += step;
+            self.0
+        }
+    }
+
+    let mut counter = Counter(0);
+
+    assert_eq!(add!(&mut counter), 1);
+    assert_eq!(add!(&mut counter, 2), 3);
+    assert_eq!(add!(self = &mut counter, step = 3), 6);
+}
+
+#[test]
+fn value_receiver() {
+    struct Counter(u32);
+
+    #[nade]
+    impl Counter {
+        #[nade]
+        fn into_inner(self, #[nade] step: u32) -> u32 {
+            self.0 + step
+        }
+
+        #[nade]
+        fn into_mut_inner(mut self, #[nade(1)] step: u32) -> u32 {
+            self.0 += step;
+            self.0
+        }
+    }
+
+    assert_eq!(into_inner!(Counter(1)), 1);
+    assert_eq!(into_inner!(self = Counter(1), step = 2), 3);
+    assert_eq!(into_mut_inner!(Counter(1)), 2);
+    assert_eq!(into_mut_inner!(step = 2, self = Counter(1)), 3);
+}
+
+#[test]
+fn typed_receiver() {
+    struct Counter(u32);
+
+    #[nade]
+    impl Counter {
+        #[nade]
+        #[allow(clippy::boxed_local)]
+        fn boxed(self: Box<Self>, #[nade(1)] step: u32) -> u32 {
+            self.0 + step
+        }
+    }
+
+    assert_eq!(boxed!(Box::new(Counter(1))), 2);
+    assert_eq!(boxed!(self = Box::new(Counter(1)), step = 2), 3);
+}
+
+#[test]
+fn associated_function() {
+    #[derive(Debug, PartialEq)]
+    struct Client {
+        timeout: u32,
+        retries: u32,
+    }
+
+    #[nade]
+    impl Client {
+        const DEFAULT_TIMEOUT: u32 = 30;
+
+        #[nade(macro_name = client_new)]
+        fn new(#[nade(Self::DEFAULT_TIMEOUT)] timeout: u32, #[nade(3)] retries: u32) -> Self {
+            Self { timeout, retries }
+        }
+
+        fn with_retries(retries: u32, #[nade(Self::DEFAULT_TIMEOUT * 2)] timeout: u32) -> Self {
+            Self { timeout, retries }
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Server {
+        port: u16,
+    }
+
+    #[nade]
+    impl Server {
+        #[nade(macro_name = server_new)]
+        fn new(#[nade(8080)] port: u16) -> Self {
+            Self { port }
+        }
+    }
+
+    assert_eq!(
+        client_new!(),
+        Client {
+            timeout: 30,
+            retries: 3
+        }
+    );
+    assert_eq!(
+        client_new!(retries = 5),
+        Client {
+            timeout: 30,
+            retries: 5
+        }
+    );
+    assert_eq!(
+        with_retries!(1),
+        Client {
+            timeout: 60,
+            retries: 1
+        }
+    );
+    assert_eq!(server_new!(), Server { port: 8080 });
+    assert_eq!(server_new!(port = 80), Server { port: 80 });
+    assert_eq!(Client::__nade_defaults_with_retries().timeout(), 60);
+}
+
+#[test]
+fn default_accessor() {
+    mod inner {
+        pub fn one() -> u32 {
+            1
+        }
+    }
+
+    #[nade]
+    fn foo(#[nade(inner::one())] a: u32, #[nade] b: String) -> String {
+        format!("{a}{b}")
+    }
+
+    assert_eq!(foo!(), "1");
+}
+
+#[test]
+fn generic_associated_function() {
+    struct Wrapper<T>(T);
+
+    #[nade]
+    impl<T> Wrapper<T> {
+        #[nade]
+        fn wrap(value: T) -> Self {
+            Self(value)
+        }
+
+        #[nade]
+        fn unwrap(self) -> T {
+            self.0
+        }
+    }
+
+    assert_eq!(unwrap!(wrap!(1)), 1);
+    assert_eq!(unwrap!(wrap!(value = "a")), "a");
+}
+
+#[test]
+fn rest_parameter() {
+    #[nade]
+    fn sum(#[nade(1)] first: u32, #[nade(rest)] rest: Vec<u32>) -> u32 {
+        first + rest.iter().sum::<u32>()
+    }
+
+    assert_eq!(sum!(), 1);
+    assert_eq!(sum!(2), 2);
+    assert_eq!(sum!(2, 3, 4), 9);
+    assert_eq!(sum!(rest = 3, rest = 4), 8);
+    assert_eq!(sum!(first = 2, rest = 3, rest = 4), 9);
+
+    #[nade]
+    fn join(
+        separator: &str,
+        #[nade(rest)] parts: impl IntoIterator<Item = &'static str>,
+    ) -> String {
+        parts.into_iter().collect::<Vec<_>>().join(separator)
+    }
+
+    assert_eq!(join!(","), "");
+    assert_eq!(join!(",", "a", "b"), "a,b");
+
+    #[nade]
+    fn count(#[nade(rest, &[0])] values: &[u32]) -> usize {
+        values.len()
+    }
+
+    assert_eq!(count!(), 1);
+    assert_eq!(count!(1, 2, 3), 3);
+
+    #[nade]
+    fn pair(#[nade(rest)] values: [u32; 2]) -> u32 {
+        values[0] * values[1]
+    }
+
+    assert_eq!(pair!(2, 3), 6);
+}
+
+#[test]
+fn passing_mode() {
+    #[nade]
+    fn open(
+        path: &str,
+        #[nade(named, false)] create: bool,
+        #[nade(named, false)] append: bool,
+    ) -> String {
+        format!("{path} {create} {append}")
+    }
+
+    assert_eq!(open!("a"), "a false false");
+    assert_eq!(open!("a", append = true), "a false true");
+    assert_eq!(open!(path = "a", create = true), "a true false");
+
+    #[nade]
+    fn scale(#[nade(positional)] value: u32, #[nade(2)] factor: u32) -> u32 {
+        value * factor
+    }
+
+    assert_eq!(scale!(3), 6);
+    assert_eq!(scale!(3, factor = 3), 9);
+
+    #[nade(all_named)]
+    fn range(
+        #[nade(positional)] start: u32,
+        #[nade(10)] end: u32,
+        #[nade(1)] step: u32,
+    ) -> Vec<u32> {
+        (start..end).step_by(step as usize).collect()
+    }
+
+    assert_eq!(range!(8), vec![8, 9]);
+    assert_eq!(range!(0, end = 6, step = 2), vec![0, 2, 4]);
+}
+
+#[test]
+fn alias() {
+    #[nade]
+    fn connect(
+        #[nade(alias = addr, alias = host)] address: &str,
+        #[nade(alias = t, 30)] timeout: u32,
+    ) -> String {
+        format!("{address}:{timeout}")
+    }
+
+    assert_eq!(connect!("a"), "a:30");
+    assert_eq!(connect!(address = "a", timeout = 1), "a:1");
+    assert_eq!(connect!(addr = "a", t = 2), "a:2");
+    assert_eq!(connect!(t = 3, host = "b"), "b:3");
+
+    #[nade]
+    struct Point {
+        #[nade(alias = first)]
+        x: u32,
+        #[nade(alias = second, 0)]
+        y: u32,
+    }
+
+    let p = Point!(first = 1, second = 2);
+    assert_eq!((p.x, p.y), (1, 2));
+}
+
+#[test]
+fn default_refers_to_earlier_parameter() {
+    #[nade]
+    fn range(
+        start: u32,
+        #[nade(start + 10)] end: u32,
+        #[nade((end - start) / 5)] step: u32,
+    ) -> Vec<u32> {
+        (start..end).step_by(step as usize).collect()
+    }
+
+    let start = 100;
+
+    assert_eq!(range!(0), vec![0, 2, 4, 6, 8]);
+    assert_eq!(range!(0, 5), vec![0, 1, 2, 3, 4]);
+    assert_eq!(range!(end = 4, start = 1, step = 2), vec![1, 3]);
+    assert_eq!(range!(start), (100..110).step_by(2).collect::<Vec<_>>());
+
+    #[nade]
+    fn first(items: &[String], #[nade(items.len().min(1))] count: usize) -> &[String] {
+        &items[..count]
+    }
+
+    assert_eq!(first!(&[String::from("a"), String::from("b")]).len(), 1);
+
+    #[nade]
+    fn measure(f: impl Fn(&str) -> usize, s: &str, #[nade(f(s))] n: usize) -> bool {
+        f(s) == n
+    }
+
+    assert!(measure!(|s| s.len(), "abc"));
+    assert!(!measure!(|s| s.len(), "abc", 1));
+
+    #[nade]
+    struct Buffer {
+        data: Vec<u8>,
+        #[nade(data.len() * 2)]
+        capacity: usize,
+    }
+
+    let buffer = Buffer!(vec![1, 2]);
+    assert_eq!((buffer.data, buffer.capacity), (vec![1, 2], 4));
+}
+
+#[test]
+fn default_refers_to_format_capture() {
+    #[nade]
+    struct Greeting {
+        name: &'static str,
+        #[nade(format!("hello {name}"))]
+        msg: String,
+    }
+
+    let greeting = Greeting!("nade");
+    assert_eq!(
+        (greeting.name, greeting.msg.as_str()),
+        ("nade", "hello nade")
+    );
+    assert_eq!(Greeting!("nade", String::from("hi")).msg, "hi");
+}
+
+#[test]
+fn default_binds_closure_and_let() {
+    #[nade]
+    fn sum(#[nade([1, 2].iter().map(|b| b * 2).sum())] a: u32, b: u32) -> u32 {
+        a + b
+    }
+
+    assert_eq!(sum!(b = 1), 7);
+
+    #[nade]
+    fn double(
+        #[nade({
+            let b = 3;
+            b * 2
+        })]
+        a: u32,
+        b: u32,
+    ) -> u32 {
+        a + b
+    }
+
+    assert_eq!(double!(b = 1), 7);
+}
+
+#[test]
+fn evaluation_order() {
+    use std::cell::RefCell;
+
+    #[nade]
+    fn pair(a: u32, #[nade(0)] b: u32, #[nade(rest)] rest: Vec<u32>) -> (u32, u32, Vec<u32>) {
+        (a, b, rest)
+    }
+
+    let order = RefCell::new(Vec::new());
+    let log = |value: u32| {
+        order.borrow_mut().push(value);
+        value
+    };
+
+    assert_eq!(pair!(b = log(2), a = log(1)), (1, 2, vec![]));
+    assert_eq!(*order.borrow(), [2, 1]);
+
+    order.borrow_mut().clear();
+    assert_eq!(
+        pair!(rest = log(3), b = log(2), a = log(1), rest = log(4)),
+        (1, 2, vec![3, 4])
+    );
+    assert_eq!(*order.borrow(), [3, 2, 1, 4]);
+
+    #[nade(reorder = allow)]
+    fn reordered(a: u32, b: u32) -> (u32, u32) {
+        (a, b)
+    }
+
+    order.borrow_mut().clear();
+    assert_eq!(reordered!(b = log(2), a = log(1)), (1, 2));
+    assert_eq!(*order.borrow(), [1, 2]);
+
+    // the arguments without side effects are passed in the call, with the parameter types
+    #[nade]
+    fn apply(f: impl Fn(&str) -> usize, n: usize) -> usize {
+        f("ab") * n
+    }
+
+    assert_eq!(apply!(n = 2, f = |s| s.len()), 4);
+
+    #[nade]
+    fn push(v: &mut Vec<u32>, x: u32) {
+        v.push(x);
+    }
+
+    let mut items = Vec::new();
+    let r = &mut items;
+    push!(x = 1, v = r);
+    push!(x = 2, v = r);
+    assert_eq!(items, [1, 2]);
+}
+
+#[test]
+fn positional_fill() {
+    #[nade(positional = fill)]
+    fn foo(a: u32, b: u32, #[nade(0)] c: u32, #[nade(0)] d: u32) -> (u32, u32, u32, u32) {
+        (a, b, c, d)
+    }
+
+    assert_eq!(foo!(1, 2), (1, 2, 0, 0));
+    assert_eq!(foo!(a = 1, 2), (1, 2, 0, 0));
+    assert_eq!(foo!(c = 3, 1, 2, 4), (1, 2, 3, 4));
+    assert_eq!(foo!(c = 1, 1, d = 2, 3), (1, 3, 1, 2));
+
+    #[nade(positional = fill)]
+    fn bar(#[nade(named, 0)] a: u32, b: u32, #[nade(rest)] rest: Vec<u32>) -> (u32, u32, Vec<u32>) {
+        (a, b, rest)
+    }
+
+    assert_eq!(bar!(1, 2, 3), (0, 1, vec![2, 3]));
+    assert_eq!(bar!(b = 1, 2, a = 3, 4), (3, 1, vec![2, 4]));
+}
+
+#[test]
+fn placeholder() {
+    #[nade]
+    fn foo(a: u32, #[nade(2)] b: u32, #[nade(a + 1)] c: u32, #[nade(4)] d: u32) -> [u32; 4] {
+        [a, b, c, d]
+    }
+
+    assert_eq!(foo!(1, _, _, 5), [1, 2, 2, 5]);
+    assert_eq!(foo!(1, _, 3), [1, 2, 3, 4]);
+    assert_eq!(foo!(1, _, d = 0), [1, 2, 2, 0]);
+
+    #[nade(positional = fill)]
+    fn bar(#[nade(1)] a: u32, b: u32, #[nade(rest)] rest: Vec<u32>) -> (u32, u32, Vec<u32>) {
+        (a, b, rest)
+    }
+
+    assert_eq!(bar!(_, 2, 3), (1, 2, vec![3]));
+    assert_eq!(bar!(b = 2, _, 3), (1, 2, vec![3]));
+}
+
+#[test]
+fn named_shorthand() {
+    #[nade]
+    fn connect(address: &str, #[nade(30)] timeout: u32, #[nade(3)] retries: u32) -> String {
+        format!("{address} {timeout} {retries}")
+    }
+
+    let address = "a";
+    let timeout = 10;
+    let retries = 5;
+
+    assert_eq!(connect!(address=), "a 30 3");
+    assert_eq!(connect!(address=, retries=), "a 30 5");
+    assert_eq!(connect!(timeout=, address = "b", retries=,), "b 10 5");
+    assert_eq!(connect!(address, timeout=), "a 10 3");
+}
+
+#[test]
+fn spread_argument() {
+    use std::cell::RefCell;
+
+    #[derive(Clone)]
+    struct Options {
+        timeout: u32,
+        retries: u32,
+        verbose: bool,
+    }
+
+    #[nade]
+    fn connect(
+        address: &str,
+        #[nade(30)] timeout: u32,
+        #[nade(3)] retries: u32,
+        #[nade(false)] verbose: bool,
+    ) -> String {
+        format!("{address} {timeout} {retries} {verbose}")
+    }
+
+    let options = Options {
+        timeout: 10,
+        retries: 5,
+        verbose: true,
+    };
+
+    assert_eq!(connect!("a", retries = 1, ..options.clone()), "a 10 1 true");
+    assert_eq!(connect!("a", ..options.clone() { timeout }), "a 10 3 false");
+    assert_eq!(connect!(address = "b", _, ..options), "b 30 5 true");
+
+    let order = RefCell::new(Vec::new());
+    let log = |value: u32| {
+        order.borrow_mut().push(value);
+        Options {
+            timeout: value,
+            retries: value,
+            verbose: false,
+        }
+    };
+
+    assert_eq!(
+        connect!(timeout = log(1).timeout, address = "c", ..log(2)),
+        "c 1 2 false"
+    );
+    assert_eq!(*order.borrow(), [1, 2]);
+
+    struct Named {
+        name: String,
+        timeout: u32,
+    }
+
+    let named = Named {
+        name: String::from("d"),
+        timeout: 20,
+    };
+
+    // only the fields passed are moved out of a place, like the struct update syntax
+    assert_eq!(connect!("d", ..named { timeout }), "d 20 3 false");
+    assert_eq!(connect!(&named.name, ..&named { timeout }), "d 20 3 false");
+    assert_eq!(named.name, "d");
+
+    // a source that covers only some of the parameters, the others fall back to their defaults
+    struct Partial {
+        retries: u32,
+        verbose: bool,
+    }
+
+    let partial = Partial {
+        retries: 7,
+        verbose: true,
+    };
+
+    assert_eq!(connect!("e", ..&partial { retries }), "e 30 7 false");
+    assert_eq!(connect!("e", _, ..&partial), "e 30 7 true");
+    assert_eq!(connect!("e", _, verbose = false, ..partial), "e 30 7 false");
+}
+
+#[test]
+fn forward() {
+    #[nade]
+    fn fetch(
+        url: &str,
+        #[nade(30)] timeout: u32,
+        #[nade(timeout / 10)] retries: u32,
+        #[nade(false)] verbose: bool,
+    ) -> String {
+        format!("{url} {timeout} {retries} {verbose}")
+    }
+
+    #[nade(forward = fetch)]
+    fn fetch_json(url: &str, timeout: u32, retries: u32, #[nade(true)] verbose: bool) -> String {
+        fetch(url, timeout, retries, verbose) + " json"
+    }
+
+    assert_eq!(fetch_json!("a"), "a 30 3 true json");
+    assert_eq!(fetch_json!("a", timeout = 50), "a 50 5 true json");
+    assert_eq!(
+        fetch_json!(retries = 1, url = "b", verbose = false),
+        "b 30 1 false json"
+    );
 }
@@ -0,0 +1,105 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[test]
+fn builder() {
+    #[nade(builder)]
+    fn foo(#[nade(42)] a: u32, b: u32, #[nade] c: String, d: u32) -> String {
+        format!("{a}-{b}-{c}-{d}")
+    }
+
+    assert_eq!(FooBuilder::new().b(1).d(2).call(), "42-1--2");
+    assert_eq!(
+        FooBuilder::new().c("c".to_string()).d(2).a(3).b(1).call(),
+        "3-1-c-2"
+    );
+    assert_eq!(FooBuilder::default().b(1).d(2).call(), foo!(b = 1, d = 2));
+}
+
+#[test]
+fn generic_builder() {
+    #[nade(builder)]
+    fn join<T: AsRef<str>, const N: usize>(parts: [T; N], #[nade(",")] separator: &str) -> String {
+        parts
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    assert_eq!(JoinBuilder::new().parts(["a", "b"]).call(), "a,b");
+    assert_eq!(
+        JoinBuilder::new()
+            .separator("-")
+            .parts([String::from("a"), String::from("b")])
+            .call(),
+        join!(["a", "b"], "-")
+    );
+}
+
+#[test]
+fn lifetime_builder() {
+    #[nade(builder)]
+    fn first<'a>(parts: &[&'a str], #[nade("")] fallback: &'a str) -> &'a str {
+        parts.first().copied().unwrap_or(fallback)
+    }
+
+    assert_eq!(FirstBuilder::new().parts(&["a"]).call(), "a");
+    assert_eq!(
+        FirstBuilder::new().parts(&[]).fallback("b").call(),
+        first!(&[], "b")
+    );
+}
+
+#[test]
+fn elided_lifetime_builder() {
+    #[nade(builder)]
+    fn trim(s: &str, #[nade(' ')] pat: char) -> &str {
+        s.trim_matches(pat)
+    }
+
+    assert_eq!(TrimBuilder::new().s(" a ").call(), "a");
+    assert_eq!(
+        TrimBuilder::new().pat('-').s("-a-").call(),
+        trim!("-a-", '-')
+    );
+}
+
+#[test]
+fn builder_in_steps() {
+    #[nade(builder)]
+    fn sum(a: u32, #[nade(2)] b: u32, c: u32) -> u32 {
+        a + b + c
+    }
+
+    let builder = SumBuilder::new().a(1);
+    let builder = builder.b(3);
+
+    assert_eq!(builder.c(4).call(), sum!(1, 3, 4));
+}
+
+#[test]
+fn named_pattern_builder() {
+    #[nade(builder)]
+    fn area(#[nade(name = size)] (width, height): (u32, u32), #[nade(1)] mut scale: u32) -> u32 {
+        scale *= width * height;
+        scale
+    }
+
+    assert_eq!(AreaBuilder::new().size((2, 3)).call(), 6);
+    assert_eq!(
+        AreaBuilder::new().scale(2).size((2, 3)).call(),
+        area!(size = (2, 3), scale = 2)
+    );
+}
+
+#[test]
+fn default_refers_to_earlier_parameter_builder() {
+    #[nade(builder)]
+    fn range(start: u32, #[nade(start + 3)] end: u32) -> Vec<u32> {
+        (start..end).collect()
+    }
+
+    assert_eq!(RangeBuilder::new().start(1).call(), range!(1));
+    assert_eq!(RangeBuilder::new().end(2).start(0).call(), range!(0, 2));
+}
@@ -3,4 +3,22 @@ fn compile_fail() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/compile_fail/01_not_find_function_error.rs");
     t.compile_fail("tests/compile_fail/02_default_argument_unhygienic_error.rs");
+    t.compile_fail("tests/compile_fail/03_struct_required_field_error.rs");
+    t.compile_fail("tests/compile_fail/04_builder_required_parameter_error.rs");
+    t.compile_fail("tests/compile_fail/05_passing_mode_error.rs");
+    t.compile_fail("tests/compile_fail/06_alias_duplicate_error.rs");
+    t.compile_fail("tests/compile_fail/07_default_forward_reference_error.rs");
+    t.compile_fail("tests/compile_fail/08_default_argument_type_error.rs");
+    t.compile_fail("tests/compile_fail/09_default_argument_capture_error.rs");
+    t.compile_fail("tests/compile_fail/10_positional_fill_error.rs");
+    t.compile_fail("tests/compile_fail/11_placeholder_error.rs");
+    t.compile_fail("tests/compile_fail/12_argument_suggestion_error.rs");
+    t.compile_fail("tests/compile_fail/13_multiple_arguments_error.rs");
+    t.compile_fail("tests/compile_fail/14_spread_argument_error.rs");
+    t.compile_fail("tests/compile_fail/15_foreign_function_error.rs");
+    t.compile_fail("tests/compile_fail/16_default_receiver_error.rs");
+    t.compile_fail("tests/compile_fail/17_signature_note_error.rs");
+    t.compile_fail("tests/compile_fail/18_forward_builder_error.rs");
+    t.compile_fail("tests/compile_fail/19_foreign_function_unsafe_argument_error.rs");
+    t.compile_fail("tests/compile_fail/20_generic_impl_default_type_error.rs");
 }
@@ -0,0 +1,13 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+struct Config {
+    #[nade(1)]
+    a: u32,
+    b: u32,
+}
+
+fn main() {
+    let _ = Config!(a = 2);
+}
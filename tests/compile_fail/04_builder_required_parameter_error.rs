@@ -0,0 +1,11 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade(builder)]
+fn foo(a: u32, #[nade] b: u32) -> u32 {
+    a + b
+}
+
+fn main() {
+    let _ = FooBuilder::new().b(1).call();
+}
@@ -0,0 +1,23 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+fn open(path: &str, #[nade(named, false)] create: bool) -> String {
+    format!("{path} {create}")
+}
+
+#[nade]
+fn scale(#[nade(positional)] value: u32, #[nade(2)] factor: u32) -> u32 {
+    value * factor
+}
+
+#[nade(all_named)]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn main() {
+    let _ = open!("a", true);
+    let _ = scale!(value = 3);
+    let _ = add!(a = 1, 2);
+}
@@ -0,0 +1,11 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+fn connect(#[nade(alias = addr, alias = host)] address: &str) -> String {
+    address.to_string()
+}
+
+fn main() {
+    let _ = connect!(addr = "a", host = "b");
+}
@@ -0,0 +1,11 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+fn range(#[nade(end - 10)] start: u32, end: u32) -> Vec<u32> {
+    (start..end).collect()
+}
+
+fn main() {
+    let _ = range!(end = 20);
+}
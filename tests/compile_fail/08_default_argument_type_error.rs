@@ -0,0 +1,15 @@
+#![allow(unused_imports)]
+
+use nade::nade;
+
+#[nade]
+fn foo(#[nade("one")] a: u32, #[nade(a + 1)] b: u32) -> u32 {
+    a + b
+}
+
+#[nade]
+fn bar(a: u32, #[nade(a.to_string())] b: u32) -> u32 {
+    a + b
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+fn foo<T: Into<u32>>(#[nade(config)] a: T) -> u32 {
+    a.into()
+}
+
+fn main() {
+    let config = 1u32;
+
+    let _ = foo!();
+    let _ = nade_helper!(() (a: u32 = config) (u32::from));
+}
@@ -0,0 +1,11 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade(positional = fill)]
+fn foo(a: u32, #[nade(0)] b: u32) -> u32 {
+    a + b
+}
+
+fn main() {
+    let _ = foo!(b = 1, 2, 3);
+}
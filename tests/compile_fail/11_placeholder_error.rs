@@ -0,0 +1,12 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+fn foo(a: u32, #[nade(0)] b: u32, #[nade(rest)] rest: Vec<u32>) -> u32 {
+    a + b + rest.len() as u32
+}
+
+fn main() {
+    let _ = foo!(_, 1);
+    let _ = foo!(1, 2, _);
+}
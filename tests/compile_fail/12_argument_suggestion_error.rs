@@ -0,0 +1,12 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+fn connect(address: &str, port: u16, #[nade(alias = t, 30)] timeout: u32) -> String {
+    format!("{address}:{port} {timeout}")
+}
+
+fn main() {
+    let _ = connect!("a", 1, tiemout = 5);
+    let _ = connect!(timeout = 5);
+}
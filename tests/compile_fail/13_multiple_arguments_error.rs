@@ -0,0 +1,11 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+fn foo(a: u32, b: u32, #[nade(named, 0)] c: u32, d: u32) -> u32 {
+    a + b + c + d
+}
+
+fn main() {
+    let _ = foo!(1, a = 2, 3, 4, e = 5, b = 6);
+}
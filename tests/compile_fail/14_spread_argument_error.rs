@@ -0,0 +1,18 @@
+pub use nade::base::*;
+use nade::nade;
+
+struct Options {
+    b: u32,
+}
+
+#[nade]
+fn foo(a: u32, #[nade(0)] b: u32) -> u32 {
+    a + b
+}
+
+fn main() {
+    let options = Options { b: 1 };
+    let _ = foo!(..options, a = 1);
+    let _ = foo!(1, ..options { b, c });
+    let _ = foo!(..options, zz = 1);
+}
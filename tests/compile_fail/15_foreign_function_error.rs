@@ -0,0 +1,9 @@
+pub use nade::base::*;
+use nade::nade;
+
+extern "C" {
+    #[nade]
+    fn abs(#[nade(-7)] x: i32) -> i32;
+}
+
+fn main() {}
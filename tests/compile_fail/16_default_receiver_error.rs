@@ -0,0 +1,17 @@
+pub use nade::base::*;
+use nade::nade;
+
+struct Counter(u32);
+
+#[nade]
+impl Counter {
+    #[nade]
+    fn get(&self, #[nade(self.0)] step: u32) -> u32 {
+        self.0 + step
+    }
+}
+
+fn main() {
+    let counter = Counter(1);
+    let _ = get!(&counter);
+}
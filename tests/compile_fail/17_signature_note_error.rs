@@ -0,0 +1,15 @@
+pub use nade::base::*;
+
+pub mod m {
+    use nade::nade;
+
+    #[nade(module_path = $crate::m)]
+    pub fn foo(#[nade(42)] a: u32, b: u32) -> u32 {
+        a + b
+    }
+}
+
+fn main() {
+    let _ = m::foo!(c = 1);
+    let _ = m::foo!(a = 1, d = 2, e = 3);
+}
@@ -0,0 +1,17 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+fn fetch(url: &str, #[nade(30)] timeout: u32) -> String {
+    format!("{url} {timeout}")
+}
+
+#[nade(builder, forward = fetch)]
+fn fetch_json(url: &str, timeout: u32) -> String {
+    fetch(url, timeout) + " json"
+}
+
+fn main() {
+    let _ = fetch!("a");
+    let _ = fetch_json!("a");
+}
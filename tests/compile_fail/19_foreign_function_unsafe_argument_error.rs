@@ -0,0 +1,16 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+extern "C" {
+    fn abs(#[nade(-7)] x: i32) -> i32;
+}
+
+fn main() {
+    let value = -3;
+    let ptr = &value as *const i32;
+
+    // the `unsafe` block of the call doesn't cover the arguments
+    let _ = abs!(*ptr);
+    let _ = abs!(x = *ptr);
+}
@@ -0,0 +1,20 @@
+#![allow(unused_imports)]
+
+use nade::nade;
+
+struct Wrapper<T>(T);
+
+#[nade]
+impl<T> Wrapper<T> {
+    #[nade]
+    fn scale(&self, #[nade("one")] factor: u32) -> u32 {
+        factor
+    }
+
+    #[nade]
+    fn replace(self, #[nade(Self(0))] other: Self) -> Self {
+        other
+    }
+}
+
+fn main() {}
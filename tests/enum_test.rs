@@ -0,0 +1,68 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[test]
+fn struct_variant() {
+    #[nade]
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Click {
+            x: i32,
+            #[nade]
+            y: i32,
+        },
+        #[nade(macro_name = key)]
+        KeyPress {
+            #[nade('a')]
+            key: char,
+            #[nade(false)]
+            shift: bool,
+        },
+        Quit,
+    }
+
+    assert_eq!(event_click!(x = 1), Event::Click { x: 1, y: 0 });
+    assert_eq!(event_click!(1, 2), Event::Click { x: 1, y: 2 });
+    assert_eq!(
+        key!(),
+        Event::KeyPress {
+            key: 'a',
+            shift: false
+        }
+    );
+    assert_eq!(
+        key!(shift = true),
+        Event::KeyPress {
+            key: 'a',
+            shift: true
+        }
+    );
+    assert_ne!(key!(), Event::Quit);
+}
+
+#[test]
+fn tuple_variant() {
+    #[nade]
+    #[derive(Debug, PartialEq)]
+    enum Error<T> {
+        Io(u32, #[nade("io error")] &'static str),
+        Custom(T),
+    }
+
+    assert_eq!(error_io!(1), Error::<()>::Io(1, "io error"));
+    assert_eq!(error_io!(1, "other"), Error::<()>::Io(1, "other"));
+    assert_eq!(error_custom!(1), Error::Custom(1));
+}
+
+#[test]
+fn acronym_variant() {
+    #[nade]
+    #[derive(Debug, PartialEq)]
+    enum HTTPError {
+        NotFound(#[nade("/")] &'static str),
+        IOError { code: u32 },
+    }
+
+    assert_eq!(http_error_not_found!(), HTTPError::NotFound("/"));
+    assert_eq!(http_error_io_error!(1), HTTPError::IOError { code: 1 });
+}
@@ -0,0 +1,49 @@
+use std::ffi::{c_char, c_int, CStr};
+
+pub use nade::base::*;
+use nade::nade;
+
+#[nade]
+extern "C" {
+    fn abs(#[nade(-7)] x: c_int) -> c_int;
+
+    #[nade]
+    fn snprintf(buf: *mut c_char, size: usize, format: *const c_char, args: ...) -> c_int;
+
+    // no macro is generated for it
+    fn labs(x: i64) -> i64;
+}
+
+#[test]
+fn foreign_function() {
+    assert_eq!(abs!(), 7);
+    assert_eq!(abs!(x = -3), 3);
+    assert_eq!(unsafe { labs(-2) }, 2);
+}
+
+#[test]
+fn variadic_foreign_function() {
+    let mut buf = [0 as c_char; 32];
+
+    let len = snprintf!(
+        buf.as_mut_ptr(),
+        buf.len(),
+        c"%d-%d".as_ptr(),
+        1 as c_int,
+        2 as c_int
+    );
+    assert_eq!(len, 3);
+    assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }, c"1-2");
+
+    let len = snprintf!(
+        format = c"%d".as_ptr(),
+        size = buf.len(),
+        buf = buf.as_mut_ptr(),
+        args = 42 as c_int
+    );
+    assert_eq!(len, 2);
+    assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }, c"42");
+
+    let len = snprintf!(buf.as_mut_ptr(), buf.len(), c"none".as_ptr());
+    assert_eq!(len, 4);
+}
@@ -13,8 +13,86 @@ pub mod foo {
     }
 }
 
+pub mod qux {
+    use nade::nade;
+
+    use self::inner::one;
+
+    mod inner {
+        pub fn one() -> usize {
+            1
+        }
+    }
+
+    #[nade(module_path = $crate::qux)]
+    pub fn bar(#[nade(one())] a: usize, #[nade(a * 2)] b: usize) -> usize {
+        a + b
+    }
+}
+
+// the accessors are imported together with the function, without `module_path`
+pub mod imported {
+    use nade::nade;
+
+    use self::inner::one;
+
+    mod inner {
+        pub fn one() -> usize {
+            1
+        }
+    }
+
+    #[nade]
+    pub fn zero<T: Default>(#[nade(T::default())] value: T) -> T {
+        value
+    }
+
+    #[nade]
+    pub fn two(#[nade(one() + 1)] value: usize) -> usize {
+        value
+    }
+}
+
+// the accessors of `foo_bar` and `foo` are not both named `__nade_default_foo_bar_a`
+pub mod accessor {
+    use nade::nade;
+
+    #[nade(module_path = $crate::accessor)]
+    pub fn foo_bar(#[nade(1)] a: u32) -> u32 {
+        a
+    }
+
+    #[nade(module_path = $crate::accessor)]
+    pub fn foo(#[nade(2)] bar_a: u32) -> u32 {
+        bar_a
+    }
+}
+
+pub mod client {
+    use nade::nade;
+
+    #[nade(module_path = $crate::client, macro_name = make_client, macro_vis = pub)]
+    pub(crate) fn new(#[nade(80)] port: u16) -> u16 {
+        port
+    }
+}
+
+pub mod server {
+    use nade::nade;
+
+    #[nade(module_path = $crate::server, macro_name = make_server)]
+    pub fn new(#[nade(8080)] port: u16) -> u16 {
+        port
+    }
+}
+
 use nade::nade;
 
+#[nade(macro_name = make_vec)]
+fn vec(#[nade(3)] len: usize) -> Vec<usize> {
+    (0..len).collect()
+}
+
 #[nade(module_path = $crate)]
 pub fn bar(#[nade($crate::baz())] a: usize) -> usize {
     a
@@ -57,12 +135,30 @@ fn custom_path(a: usize) -> usize {
     a
 }
 
+#[test]
+fn imported_default() {
+    use imported::{two, zero};
+
+    let value: u32 = zero!();
+    assert_eq!(value, 0);
+    assert_eq!(zero!(value = "a"), "a");
+    assert_eq!(two!(), 2);
+}
+
 #[test]
 fn path_test() {
     assert_eq!(bar!(), 1);
     assert_eq!(foo::bar!(), 1);
+    assert_eq!(qux::bar!(), 3);
+    assert_eq!(qux::bar!(2), 6);
+    assert_eq!(qux::bar::a(), 1);
+    assert_eq!(accessor::foo_bar!(), 1);
+    assert_eq!(accessor::foo!(), 2);
     assert_eq!(default_path!(1), 1);
     assert_eq!(custom_macro_v_path!(1), 1);
     assert_eq!(custom_nade_helper_path!(1), 1);
     assert_eq!(custom_path!(1), 1);
+    assert_eq!(client::make_client!(), 80);
+    assert_eq!(server::make_server!(port = 1), 1);
+    assert_eq!(make_vec!(), vec![0, 1, 2]);
 }
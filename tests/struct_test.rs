@@ -0,0 +1,78 @@
+pub use nade::base::*;
+use nade::nade;
+
+#[test]
+fn named_fields() {
+    #[nade]
+    #[derive(Debug, PartialEq)]
+    struct Config {
+        #[nade(1)]
+        a: u32,
+        b: String,
+        /// default of `u32`
+        #[nade]
+        c: u32,
+    }
+
+    assert_eq!(
+        Config!(b = "b".to_string()),
+        Config {
+            a: 1,
+            b: "b".to_string(),
+            c: 0
+        }
+    );
+    assert_eq!(
+        Config!(2, "b".to_string(), 3),
+        Config {
+            a: 2,
+            b: "b".to_string(),
+            c: 3
+        }
+    );
+    assert_eq!(
+        Config!(c = 3, b = String::new()),
+        Config {
+            a: 1,
+            b: String::new(),
+            c: 3
+        }
+    );
+}
+
+#[test]
+fn generic_struct() {
+    #[nade]
+    #[derive(Debug, PartialEq)]
+    struct Pair<T> {
+        first: T,
+        #[nade]
+        second: T,
+    }
+
+    assert_eq!(
+        Pair!(first = 1),
+        Pair {
+            first: 1,
+            second: 0
+        }
+    );
+    assert_eq!(
+        Pair!("a", "b"),
+        Pair {
+            first: "a",
+            second: "b"
+        }
+    );
+}
+
+#[test]
+fn tuple_struct() {
+    #[nade]
+    #[derive(Debug, PartialEq)]
+    struct Point(i32, #[nade] i32, #[nade(1)] i32);
+
+    assert_eq!(Point!(1), Point(1, 0, 1));
+    assert_eq!(Point!(1, 2), Point(1, 2, 1));
+    assert_eq!(Point!(1, 2, 3), Point(1, 2, 3));
+}
@@ -0,0 +1,29 @@
+pub use nade::base::*;
+
+mod math {
+    pub fn clamp(value: i32, min: i32, max: i32) -> i32 {
+        value.max(min).min(max)
+    }
+}
+
+nade::wrap! {
+    /// Splits a string.
+    pub fn str::split_at(s: &str, #[nade(1)] mid: usize) -> (&str, &str);
+
+    #[nade(macro_name = clamp_value)]
+    fn crate::math::clamp(value: i32, #[nade(0)] min: i32, #[nade(100)] max: i32) -> i32;
+
+    fn std::cmp::max(#[nade(alias = a)] v1: u32, #[nade(alias = b, 0)] v2: u32) -> u32;
+}
+
+#[test]
+fn wrap() {
+    assert_eq!(split_at!("abc"), ("a", "bc"));
+    assert_eq!(split_at!(mid = 2, s = "abc"), ("ab", "c"));
+
+    assert_eq!(clamp_value!(-5), 0);
+    assert_eq!(clamp_value!(500, max = 50), 50);
+
+    assert_eq!(max!(a = 3), 3);
+    assert_eq!(max!(b = 5, a = 3), 5);
+}